@@ -1,3 +1,4 @@
+use crate::bignum::BigInt;
 use crate::token::Token;
 
 #[derive(Debug, Clone)]
@@ -14,27 +15,38 @@ pub enum Expr {
     Literal(Literal),
     Variable(String),
     Grouping(Box<Expr>),
+    // Interpolated string: literal pieces and expressions in order
+    Interpolation(Vec<Expr>),
     // Function call
     Call {
         callee: Box<Expr>,
         arguments: Vec<Expr>,
+        line: usize,
     },
     // Lambda expression: fn(params) expr or fn(params) { stmts }
+    // Parameters may be irrefutable patterns: fn(Punt(x, y)) x + y
     Lambda {
-        params: Vec<String>,
+        params: Vec<Pattern>,
         body: LambdaBody,
+        line: usize,
     },
     // List literal: [a, b, c]
     List(Vec<Expr>),
+    // Tuple literal: (a, b)
+    Tuple(Vec<Expr>),
+    // Dictionary literal: {"a": 1, "b": 2}
+    Dict(Vec<(Expr, Expr)>),
     // Index expression: list[index]
     Index {
         object: Box<Expr>,
         index: Box<Expr>,
+        line: usize,
     },
     // Pattern matching expression: pas(value) { geval Pattern => expr ... }
     Match {
         value: Box<Expr>,
         arms: Vec<MatchArm>,
+        line: usize,
     },
     // Inline if expression: as(condition) then_expr anders else_expr
     IfExpr {
@@ -42,11 +54,26 @@ pub enum Expr {
         then_branch: Box<Expr>,
         else_branch: Box<Expr>,
     },
-    // Member access for modules: module.member
+    // Member access for modules: module.member, or named fields: punt.x
     MemberAccess {
         object: Box<Expr>,
         member: String,
     },
+    // Constructor call with named arguments: Punt(x: 1, y: 2)
+    NamedCall {
+        callee: Box<Expr>,
+        arguments: Vec<(String, Expr)>,
+    },
+    // Functional record update: punt met { x: 3 }
+    RecordUpdate {
+        object: Box<Expr>,
+        fields: Vec<(String, Expr)>,
+    },
+    // Early return: waarde? unwraps Sommige/Ok or returns the Niks/Fout
+    Propagate {
+        value: Box<Expr>,
+        line: usize,
+    },
 }
 
 /// Represents a type constructor definition
@@ -70,6 +97,22 @@ pub enum Pattern {
         name: String,
         fields: Vec<Pattern>,
     },
+    /// List pattern with an exact length: [a, b]
+    List(Vec<Pattern>),
+    /// Tuple pattern: (a, b)
+    Tuple(Vec<Pattern>),
+    /// Inclusive numeric range pattern: 1..9
+    Range {
+        start: Literal,
+        end: Literal,
+    },
+    /// Or-pattern: Rooi of Groen (or Rooi || Groen)
+    Or(Vec<Pattern>),
+    /// As-binding that binds the whole value while destructuring: Tak(l, r) @ heel
+    Binding {
+        name: String,
+        pattern: Box<Pattern>,
+    },
 }
 
 /// A single match arm: geval Pattern => body
@@ -88,6 +131,8 @@ pub enum LambdaBody {
 #[derive(Debug, Clone)]
 pub enum Literal {
     Number(f64),
+    Integer(i64),
+    BigInt(BigInt),
     Boolean(bool),
     String(String),
     Nil,
@@ -101,6 +146,12 @@ pub enum Stmt {
         name: String,
         initializer: Expr,
     },
+    // Destructuring declaration: laat [a, b] = paar, laat Punt(x, y) = p
+    PatternDecl {
+        pattern: Pattern,
+        initializer: Expr,
+        line: usize,
+    },
     Block(Vec<Stmt>),
     If {
         condition: Expr,
@@ -126,6 +177,18 @@ pub enum Stmt {
         name: String,
         constructors: Vec<TypeConstructor>,
     },
+    // Raise a value: gooi waarde
+    Throw {
+        value: Expr,
+        line: usize,
+    },
+    // probeer { body } vang (name) { handler }
+    Try {
+        body: Vec<Stmt>,
+        name: String,
+        handler: Vec<Stmt>,
+        line: usize,
+    },
     // Module import: laai "path" as name
     Import {
         path: String,
@@ -0,0 +1,627 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Arbitrary-precision integer: a sign and a little-endian magnitude in base 2^32.
+/// The magnitude never has trailing zero limbs, and zero is never negative.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+/// Exact fraction in lowest terms with a positive denominator
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: BigInt,
+    denom: BigInt,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        BigInt { negative: false, magnitude: Vec::new() }
+    }
+
+    pub fn from_i64(n: i64) -> Self {
+        let mut magnitude = Vec::new();
+        let mut m = n.unsigned_abs();
+        while m > 0 {
+            magnitude.push(m as u32);
+            m >>= 32;
+        }
+        BigInt { negative: n < 0, magnitude }
+    }
+
+    /// Parse digits in the given radix; None if any character isn't a digit
+    pub fn from_digits(digits: &str, radix: u32) -> Option<Self> {
+        let base = BigInt::from_i64(radix as i64);
+        let mut n = BigInt::zero();
+        for c in digits.chars() {
+            let digit = c.to_digit(radix)?;
+            n = n.mul(&base).add(&BigInt::from_i64(digit as i64));
+        }
+        Some(n)
+    }
+
+    /// Exact conversion of a whole float; None for fractions, NaN and infinity
+    pub fn from_f64(n: f64) -> Option<Self> {
+        if !n.is_finite() || n.fract() != 0.0 {
+            return None;
+        }
+        if n == 0.0 {
+            return Some(BigInt::zero());
+        }
+        let (mantissa, exponent) = decompose_f64(n.abs());
+        let value = if exponent >= 0 {
+            BigInt::from_i64(mantissa as i64).shl(exponent as usize)
+        } else {
+            BigInt::from_i64((mantissa >> (-exponent)) as i64)
+        };
+        Some(if n < 0.0 { value.neg() } else { value })
+    }
+
+    fn from_magnitude(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        let negative = negative && !magnitude.is_empty();
+        BigInt { negative, magnitude }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_one(&self) -> bool {
+        !self.negative && self.magnitude == [1]
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let mut m: u64 = 0;
+        for (i, limb) in self.magnitude.iter().enumerate() {
+            m |= (*limb as u64) << (32 * i);
+        }
+        if self.negative {
+            if m <= i64::MAX as u64 + 1 {
+                Some((m as i64).wrapping_neg())
+            } else {
+                None
+            }
+        } else {
+            i64::try_from(m).ok()
+        }
+    }
+
+    /// Upper bound on the number of bits in the magnitude
+    pub fn bits(&self) -> u64 {
+        self.magnitude.len() as u64 * 32
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let mut result = 0.0;
+        for limb in self.magnitude.iter().rev() {
+            result = result * 4294967296.0 + *limb as f64;
+        }
+        if self.negative { -result } else { result }
+    }
+
+    pub fn neg(&self) -> Self {
+        BigInt::from_magnitude(!self.negative, self.magnitude.clone())
+    }
+
+    pub fn abs(&self) -> Self {
+        BigInt::from_magnitude(false, self.magnitude.clone())
+    }
+
+    pub fn add(&self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_magnitude(self.negative, mag_add(&self.magnitude, &other.magnitude));
+        }
+        match mag_cmp(&self.magnitude, &other.magnitude) {
+            Ordering::Less => {
+                BigInt::from_magnitude(other.negative, mag_sub(&other.magnitude, &self.magnitude))
+            }
+            _ => BigInt::from_magnitude(self.negative, mag_sub(&self.magnitude, &other.magnitude)),
+        }
+    }
+
+    pub fn sub(&self, other: &BigInt) -> BigInt {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &BigInt) -> BigInt {
+        BigInt::from_magnitude(
+            self.negative != other.negative,
+            mag_mul(&self.magnitude, &other.magnitude),
+        )
+    }
+
+    /// Division rounded toward zero; the remainder takes the dividend's sign
+    fn div_rem_trunc(&self, other: &BigInt) -> (BigInt, BigInt) {
+        let (q, r) = mag_divmod(&self.magnitude, &other.magnitude);
+        (
+            BigInt::from_magnitude(self.negative != other.negative, q),
+            BigInt::from_magnitude(self.negative, r),
+        )
+    }
+
+    /// Division rounded down; the remainder takes the divisor's sign.
+    /// The divisor must not be zero.
+    pub fn div_mod_floor(&self, other: &BigInt) -> (BigInt, BigInt) {
+        let (q, r) = self.div_rem_trunc(other);
+        if !r.is_zero() && r.negative != other.negative {
+            (q.sub(&BigInt::from_i64(1)), r.add(other))
+        } else {
+            (q, r)
+        }
+    }
+
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let mut a = self.abs();
+        let mut b = other.abs();
+        while !b.is_zero() {
+            let (_, r) = a.div_rem_trunc(&b);
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    pub fn shl(&self, bits: usize) -> BigInt {
+        let mut magnitude = vec![0; bits / 32];
+        magnitude.extend(shl_bits(&self.magnitude, (bits % 32) as u32));
+        BigInt::from_magnitude(self.negative, magnitude)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => mag_cmp(&self.magnitude, &other.magnitude),
+            (true, true) => mag_cmp(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // Peel off base-10^9 chunks from the least significant end
+        let mut chunks = Vec::new();
+        let mut rest = self.magnitude.clone();
+        while !rest.is_empty() {
+            let (q, r) = mag_divmod_small(&rest, 1_000_000_000);
+            chunks.push(r);
+            rest = q;
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks.last().unwrap())?;
+        for chunk in chunks.iter().rev().skip(1) {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl Rational {
+    /// Build a fraction in lowest terms. The denominator must not be zero.
+    pub fn new(numer: BigInt, denom: BigInt) -> Self {
+        let divisor = numer.gcd(&denom);
+        let (mut numer, mut denom) = if divisor.is_zero() || divisor.is_one() {
+            (numer, denom)
+        } else {
+            (numer.div_rem_trunc(&divisor).0, denom.div_rem_trunc(&divisor).0)
+        };
+        if denom.is_negative() {
+            numer = numer.neg();
+            denom = denom.neg();
+        }
+        Rational { numer, denom }
+    }
+
+    pub fn from_integer(n: BigInt) -> Self {
+        Rational { numer: n, denom: BigInt::from_i64(1) }
+    }
+
+    /// Exact value of a finite float; None for NaN and infinity
+    pub fn from_f64(n: f64) -> Option<Self> {
+        if !n.is_finite() {
+            return None;
+        }
+        if n == 0.0 {
+            return Some(Rational::from_integer(BigInt::zero()));
+        }
+        let (mantissa, exponent) = decompose_f64(n.abs());
+        let numer = BigInt::from_i64(mantissa as i64);
+        let numer = if n < 0.0 { numer.neg() } else { numer };
+        Some(if exponent >= 0 {
+            Rational::from_integer(numer.shl(exponent as usize))
+        } else {
+            Rational::new(numer, BigInt::from_i64(1).shl((-exponent) as usize))
+        })
+    }
+
+    pub fn numer(&self) -> &BigInt {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &BigInt {
+        &self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom.is_one()
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numer.to_f64() / self.denom.to_f64()
+    }
+
+    pub fn add(&self, other: &Rational) -> Rational {
+        Rational::new(
+            self.numer.mul(&other.denom).add(&other.numer.mul(&self.denom)),
+            self.denom.mul(&other.denom),
+        )
+    }
+
+    pub fn sub(&self, other: &Rational) -> Rational {
+        Rational::new(
+            self.numer.mul(&other.denom).sub(&other.numer.mul(&self.denom)),
+            self.denom.mul(&other.denom),
+        )
+    }
+
+    pub fn mul(&self, other: &Rational) -> Rational {
+        Rational::new(self.numer.mul(&other.numer), self.denom.mul(&other.denom))
+    }
+
+    /// The divisor must not be zero
+    pub fn div(&self, other: &Rational) -> Rational {
+        Rational::new(self.numer.mul(&other.denom), self.denom.mul(&other.numer))
+    }
+
+    /// Largest integer not greater than the fraction
+    pub fn floor(&self) -> BigInt {
+        self.numer.div_mod_floor(&self.denom).0
+    }
+
+    /// Integer part, rounded toward zero
+    pub fn trunc(&self) -> BigInt {
+        self.numer.div_rem_trunc(&self.denom).0
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Denominators are positive, so cross-multiplying keeps the order
+        self.numer.mul(&other.denom).cmp(&other.numer.mul(&self.denom))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/// Split a non-negative finite float into mantissa * 2^exponent
+fn decompose_f64(n: f64) -> (u64, i32) {
+    let bits = n.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1u64 << 52) - 1);
+    if exponent == 0 {
+        // Subnormal (or zero)
+        (fraction, -1074)
+    } else {
+        (fraction | (1u64 << 52), exponent - 1075)
+    }
+}
+
+fn mag_cmp(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn mag_add(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &limb) in long.iter().enumerate() {
+        let sum = limb as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+/// a - b, where a >= b
+fn mag_sub(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let mut diff = limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        if diff < 0 {
+            diff += 1 << 32;
+            borrow = 1;
+        } else {
+            borrow = 0;
+        }
+        result.push(diff as u32);
+    }
+    result
+}
+
+fn mag_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    result
+}
+
+fn mag_divmod_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut rem = 0u64;
+    for i in (0..a.len()).rev() {
+        let cur = (rem << 32) | a[i] as u64;
+        quotient[i] = (cur / divisor as u64) as u32;
+        rem = cur % divisor as u64;
+    }
+    while quotient.last() == Some(&0) {
+        quotient.pop();
+    }
+    (quotient, rem as u32)
+}
+
+fn shl_bits(a: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return a.to_vec();
+    }
+    let mut result = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u32;
+    for &limb in a {
+        result.push((limb << shift) | carry);
+        carry = limb >> (32 - shift);
+    }
+    if carry > 0 {
+        result.push(carry);
+    }
+    result
+}
+
+fn shr_bits(a: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return a.to_vec();
+    }
+    let mut result = vec![0u32; a.len()];
+    for i in 0..a.len() {
+        let high = if i + 1 < a.len() { a[i + 1] << (32 - shift) } else { 0 };
+        result[i] = (a[i] >> shift) | high;
+    }
+    result
+}
+
+/// Long division of magnitudes (Knuth, algorithm D). The divisor must not be zero.
+fn mag_divmod(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if mag_cmp(u, v) == Ordering::Less {
+        return (Vec::new(), u.to_vec());
+    }
+    if v.len() == 1 {
+        let (q, r) = mag_divmod_small(u, v[0]);
+        let r = if r == 0 { Vec::new() } else { vec![r] };
+        return (q, r);
+    }
+
+    // Normalise so the divisor's top limb has its high bit set
+    let n = v.len();
+    let m = u.len() - n;
+    let shift = v[n - 1].leading_zeros();
+    let vn = shl_bits(v, shift);
+    let mut un = shl_bits(u, shift);
+    un.resize(u.len() + 1, 0);
+
+    let base = 1u64 << 32;
+    let mut q = vec![0u32; m + 1];
+    for j in (0..=m).rev() {
+        let num = ((un[j + n] as u64) << 32) | un[j + n - 1] as u64;
+        let mut qhat = num / vn[n - 1] as u64;
+        let mut rhat = num % vn[n - 1] as u64;
+        while qhat >= base || qhat * vn[n - 2] as u64 > ((rhat << 32) | un[j + n - 2] as u64) {
+            qhat -= 1;
+            rhat += vn[n - 1] as u64;
+            if rhat >= base {
+                break;
+            }
+        }
+
+        // Multiply and subtract
+        let mut k = 0i64;
+        for i in 0..n {
+            let p = qhat * vn[i] as u64;
+            let t = un[i + j] as i64 - k - (p & 0xFFFF_FFFF) as i64;
+            un[i + j] = t as u32;
+            k = (p >> 32) as i64 - (t >> 32);
+        }
+        let t = un[j + n] as i64 - k;
+        un[j + n] = t as u32;
+
+        q[j] = qhat as u32;
+        if t < 0 {
+            // Subtracted too much; add the divisor back
+            q[j] = q[j].wrapping_sub(1);
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = un[i + j] as u64 + vn[i] as u64 + carry;
+                un[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u32);
+        }
+    }
+
+    let mut r = shr_bits(&un[..n], shift);
+    while r.last() == Some(&0) {
+        r.pop();
+    }
+    while q.last() == Some(&0) {
+        q.pop();
+    }
+    (q, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(n: i128) -> BigInt {
+        let mut magnitude = Vec::new();
+        let mut m = n.unsigned_abs();
+        while m > 0 {
+            magnitude.push(m as u32);
+            m >>= 32;
+        }
+        BigInt::from_magnitude(n < 0, magnitude)
+    }
+
+    fn limbs(negative: bool, magnitude: &[u32]) -> BigInt {
+        BigInt::from_magnitude(negative, magnitude.to_vec())
+    }
+
+    /// Floored division on i128, the rule div_mod_floor follows
+    fn floor_div_mod(n: i128, d: i128) -> (i128, i128) {
+        let (q, r) = (n / d, n % d);
+        if r != 0 && (r < 0) != (d < 0) {
+            (q - 1, r + d)
+        } else {
+            (q, r)
+        }
+    }
+
+    #[test]
+    fn division_by_a_multi_limb_divisor() {
+        let cases = [
+            (0x1234_5678_9abc_def0_1122_3344_5566_7788, 0x1_0000_0001),
+            (0x7fff_ffff_ffff_ffff_ffff_ffff_ffff_ffff, 0xffff_ffff_ffff_ffff),
+            (0x0000_0001_0000_0000_0000_0000_0000_0000, 0x0000_0000_ffff_ffff_ffff_ffff),
+            (0x7654_3210_fedc_ba98_0123_4567_89ab_cdef, 0x0000_0003_0000_0000_0000_0007),
+            (12345, 0x1_0000_0000_0000),
+        ];
+        for (n, d) in cases {
+            let (q, r) = floor_div_mod(n, d);
+            assert_eq!(big(n).div_mod_floor(&big(d)), (big(q), big(r)), "{} / {}", n, d);
+        }
+    }
+
+    #[test]
+    fn division_corrects_an_overestimated_quotient_digit() {
+        // The first guess from the top limbs is brought down by the next limb
+        let (n, d) = (0x7fff_ffff_0000_0000_0000_0000_0000_0000, 0x8000_0000_ffff_ffff_0000_0000);
+        let (q, r) = floor_div_mod(n, d);
+        assert_eq!(big(n).div_mod_floor(&big(d)), (big(q), big(r)));
+
+        // The guess passes that test but is still one too big: add back
+        let (q, r) = limbs(false, &[3, 0, 0x8000_0000])
+            .div_mod_floor(&limbs(false, &[1, 0, 0x2000_0000]));
+        assert_eq!(q, big(3));
+        assert_eq!(r, limbs(false, &[0, 0, 0x2000_0000]));
+
+        // A divisor whose top limb needs no normalising shift
+        let (q, r) = limbs(false, &[7, 0xffff_ffff, 0xffff_ffff, 5])
+            .div_mod_floor(&limbs(false, &[0xffff_ffff, 0xffff_ffff]));
+        assert_eq!(q.mul(&limbs(false, &[0xffff_ffff, 0xffff_ffff])).add(&r), limbs(false, &[7, 0xffff_ffff, 0xffff_ffff, 5]));
+        assert!(r < limbs(false, &[0xffff_ffff, 0xffff_ffff]));
+    }
+
+    #[test]
+    fn division_floors_towards_negative_infinity() {
+        let d = 0x1_0000_0000_0000_0003;
+        let n = 0x5_0000_0000_0000_0000_0000_0011;
+        for (n, d) in [(n, d), (-n, d), (n, -d), (-n, -d), (7, 2), (-7, 2), (7, -2), (-7, -2), (-8, 2)] {
+            let (q, r) = floor_div_mod(n, d);
+            assert_eq!(big(n).div_mod_floor(&big(d)), (big(q), big(r)), "{} / {}", n, d);
+        }
+    }
+
+    #[test]
+    fn division_holds_beyond_i128() {
+        let n = BigInt::from_digits("98765432109876543210987654321098765432109876543210", 10).unwrap().neg();
+        let d = BigInt::from_digits("123456789012345678901234567", 10).unwrap();
+        let (q, r) = n.div_mod_floor(&d);
+        assert_eq!(q.mul(&d).add(&r), n);
+        assert!(!r.is_negative() && r < d);
+    }
+
+    #[test]
+    fn results_are_normalised() {
+        let n = big(1 << 64);
+        assert_eq!(n.sub(&n), BigInt::zero());
+        assert!(!big(-5).add(&big(5)).is_negative());
+        assert_eq!(big(-5).add(&big(5)).magnitude, Vec::<u32>::new());
+        assert_eq!(big(1 << 64).sub(&big(1)).magnitude, vec![u32::MAX, u32::MAX]);
+        assert_eq!(big(-(1 << 40)).to_i64(), Some(-(1 << 40)));
+        assert_eq!(big(i64::MIN as i128).to_i64(), Some(i64::MIN));
+        assert_eq!(big(i64::MAX as i128 + 1).to_i64(), None);
+    }
+
+    #[test]
+    fn rationals_are_kept_in_lowest_terms() {
+        let half = Rational::new(big(6), big(12));
+        assert_eq!((half.numer(), half.denom()), (&big(1), &big(2)));
+
+        let negative = Rational::new(big(4), big(-6));
+        assert_eq!((negative.numer(), negative.denom()), (&big(-2), &big(3)));
+        assert_eq!(Rational::new(big(-4), big(-6)), Rational::new(big(2), big(3)));
+
+        let zero = Rational::new(big(0), big(-9));
+        assert_eq!((zero.numer(), zero.denom()), (&big(0), &big(1)));
+
+        let sum = Rational::new(big(1), big(6)).add(&Rational::new(big(1), big(3)));
+        assert_eq!(sum, Rational::new(big(1), big(2)));
+        assert!(Rational::new(big(10), big(5)).is_integer());
+        assert_eq!(Rational::new(big(-7), big(2)).floor(), big(-4));
+        assert_eq!(Rational::new(big(-7), big(2)).trunc(), big(-3));
+    }
+}
@@ -29,9 +29,18 @@ pub enum OpCode {
     Subtract,           // -
     Multiply,           // *
     Divide,             // /
+    IntDivide,          // ~/ (division rounded down)
     Modulo,             // %
     Negate,             // unary -
 
+    // Bitwise (integers only)
+    BitAnd,             // &
+    BitOr,              // |
+    BitXor,             // ^
+    BitNot,             // unary ~
+    ShiftLeft,          // <<
+    ShiftRight,         // >>
+
     // Comparison
     Equal,              // ==
     NotEqual,           // !=
@@ -52,28 +61,44 @@ pub enum OpCode {
 
     // Functions
     Call(usize),        // Call function with N arguments
+    CallNamed(Vec<String>), // Call constructor with named arguments (field names in argument order)
     TailCall(usize),    // Tail call - reuse current frame
     Return,             // Return from function (with value on stack)
 
+    // Errors
+    Try(usize),         // Enter a probeer block; errors jump to the vang handler at the address
+    EndTry,             // Leave the innermost probeer block normally
+    Throw,              // Raise the value on top of the stack
+    CheckFailure,       // Push whether TOS (an Opsie or Resultaat) is Niks or Fout, without popping
+
     // Lists
     MakeList(usize),    // Create list from N values on stack
+    MakeTuple(usize),   // Create tuple from N values on stack
+    Concat(usize),      // Join N values on stack into one string
+    MakeDict(usize),    // Create dictionary from N key/value pairs on stack
     GetIndex,           // Get element at index: stack[list, index] -> value
 
     // Pattern matching
     CheckConstructor(String, usize),  // Check if TOS is constructor with name and arity
+    CheckRange(Value, Value),          // Check if TOS is a number in the inclusive range
+    CheckList(usize),                  // Check if TOS is a list with exactly N elements
+    CheckTuple(usize),                 // Check if TOS is a tuple with exactly N elements
     GetField(usize),                   // Get field at index from ADT at TOS (doesn't pop)
-    GetFieldPop(usize),                // Get field at index from ADT and pop the ADT
+    GetFieldPop(usize),                // Get field at index from ADT (or list element) and pop it
     Dup,                               // Duplicate top of stack
+    MatchFailed(String),               // Runtime error: a pattern did not match (message)
 
     // Modules
     LoadModule(String, String),        // Load module: (path, alias) -> pushes Module value
-    GetMember(String),                 // Get member from module on stack
+    GetMember(String),                 // Get member from module (or named field from ADT) on stack
+    UpdateFields(Vec<String>),         // Copy ADT below N values, replacing the named fields
 }
 
 #[derive(Debug, Clone)]
 pub struct Chunk {
     pub code: Vec<OpCode>,
     pub constants: Vec<Value>,
+    pub lines: Vec<usize>, // Source line of each instruction
 }
 
 impl Chunk {
@@ -81,11 +106,13 @@ impl Chunk {
         Chunk {
             code: Vec::new(),
             constants: Vec::new(),
+            lines: Vec::new(),
         }
     }
 
-    pub fn write(&mut self, op: OpCode) -> usize {
+    pub fn write(&mut self, op: OpCode, line: usize) -> usize {
         self.code.push(op);
+        self.lines.push(line);
         self.code.len() - 1
     }
 
@@ -98,6 +125,7 @@ impl Chunk {
         match &mut self.code[offset] {
             OpCode::Jump(ref mut addr) => *addr = target,
             OpCode::JumpIfFalse(ref mut addr) => *addr = target,
+            OpCode::Try(ref mut addr) => *addr = target,
             _ => panic!("Tried to patch non-jump instruction"),
         }
     }
@@ -1,13 +1,15 @@
-use crate::ast::{Expr, LambdaBody, Literal, Pattern, Stmt};
+use crate::ast::{Expr, LambdaBody, Literal, Pattern, Stmt, TypeConstructor};
 use crate::bytecode::{Chunk, OpCode};
+use crate::numeric;
 use crate::token::TokenType;
 use crate::value::{Function, TypeConstructorDef, UpvalueDescriptor, Value};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 // Hidden local variable names used for pattern matching
 const MATCH_SCRUTINEE: &str = "$match";
-const CTOR_HIDDEN_LOCAL: &str = "$ctor";
+const DESTRUCTURE_HIDDEN_LOCAL: &str = "$laat";
+const PARAM_HIDDEN_LOCAL: &str = "$param";
 
 #[derive(Debug, Clone)]
 struct Local {
@@ -42,6 +44,7 @@ struct FunctionCompiler {
     upvalues: Vec<CompilerUpvalue>, // Captured variables
     scope_depth: usize,
     arity: usize,
+    try_depth: usize, // Open probeer blocks; tail calls would skip their handlers
 }
 
 impl FunctionCompiler {
@@ -54,6 +57,7 @@ impl FunctionCompiler {
             upvalues: Vec::new(),
             scope_depth: 0,
             arity,
+            try_depth: 0,
         };
 
         // Reserve slot 0 for the function itself (or empty for scripts)
@@ -74,6 +78,8 @@ pub struct Compiler {
     enclosing: Option<Box<Compiler>>,
     functions: Vec<Rc<Chunk>>,
     exported_symbols: HashSet<String>,
+    type_constructors: HashMap<String, usize>, // Constructor name -> constructor count of its type
+    line: usize,                               // Source line recorded for emitted instructions
 }
 
 impl Compiler {
@@ -83,6 +89,8 @@ impl Compiler {
             enclosing: None,
             functions: Vec::new(),
             exported_symbols: HashSet::new(),
+            type_constructors: HashMap::new(),
+            line: 0,
         }
     }
 
@@ -91,11 +99,43 @@ impl Compiler {
     }
 
     pub fn compile(&mut self, statements: Vec<Stmt>) -> Result<(Chunk, Vec<Rc<Chunk>>), String> {
+        self.compile_script(statements, None)
+    }
+
+    /// Like `compile`, but the chunk returns the value of a trailing expression statement.
+    pub fn compile_eval(&mut self, mut statements: Vec<Stmt>) -> Result<(Chunk, Vec<Rc<Chunk>>), String> {
+        let result = match statements.last() {
+            Some(Stmt::Expression(_)) => match statements.pop() {
+                Some(Stmt::Expression(expr)) => Some(expr),
+                _ => None,
+            },
+            _ => None,
+        };
+        self.compile_script(statements, result)
+    }
+
+    fn compile_script(
+        &mut self,
+        statements: Vec<Stmt>,
+        result: Option<Expr>,
+    ) -> Result<(Chunk, Vec<Rc<Chunk>>), String> {
+        // Register top-level types first so patterns can refer to types declared later
+        for stmt in &statements {
+            if let Stmt::TypeDecl { constructors, .. } = stmt {
+                self.register_type(constructors);
+            }
+        }
+
         for stmt in statements {
             self.compile_stmt(stmt)?;
         }
-        let nil_idx = self.add_constant(Value::Nil);
-        self.emit(OpCode::Constant(nil_idx));
+        match result {
+            Some(expr) => self.compile_expr(expr)?,
+            None => {
+                let nil_idx = self.add_constant(Value::Nil);
+                self.emit(OpCode::Constant(nil_idx));
+            }
+        }
         self.emit(OpCode::Return);
 
         let main_chunk = self.current.chunk.clone();
@@ -120,12 +160,12 @@ impl Compiler {
     }
 
     fn add_local(&mut self, name: String) -> Result<(), String> {
-        // Check for duplicate in current scope
+        // Check for duplicate in current scope (hidden locals may repeat)
         for local in self.current.locals.iter().rev() {
             if local.depth < self.current.scope_depth {
                 break;
             }
-            if local.name == name {
+            if local.name == name && !name.starts_with('$') {
                 return Err(format!(
                     "Konstante '{}' is reeds in hierdie omvang gedefinieer.",
                     name
@@ -223,6 +263,8 @@ impl Compiler {
             enclosing: old_enclosing,
             functions: Vec::new(),
             exported_symbols: HashSet::new(),
+            type_constructors: HashMap::new(),
+            line: 0,
         }));
 
         // Begin function scope
@@ -275,6 +317,72 @@ impl Compiler {
                     self.emit(OpCode::DefineGlobal(name));
                 }
             }
+            Stmt::PatternDecl {
+                pattern,
+                initializer,
+                line,
+            } => {
+                self.line = line;
+                // Globals are destructured through a temporary scope
+                let is_global = self.current.scope_depth == 0;
+                if is_global {
+                    self.begin_scope();
+                }
+
+                self.compile_expr(initializer)?;
+                self.add_local(String::from(DESTRUCTURE_HIDDEN_LOCAL))?;
+                let value_slot = self.resolve_local(DESTRUCTURE_HIDDEN_LOCAL).unwrap();
+                let bindings = self.compile_destructure(&pattern, value_slot, line)?;
+
+                if is_global {
+                    // Move the bindings from their temporary slots into globals
+                    for name in bindings.into_iter().rev() {
+                        self.emit(OpCode::DefineGlobal(name));
+                        self.current.locals.pop();
+                    }
+                    // Pop the destructured value
+                    self.emit(OpCode::Pop);
+                    self.current.locals.pop();
+                    self.current.scope_depth -= 1;
+                }
+            }
+            Stmt::Throw { value, line } => {
+                self.compile_expr(value)?;
+                self.line = line;
+                self.emit(OpCode::Throw);
+            }
+            Stmt::Try {
+                body,
+                name,
+                handler,
+                line,
+            } => {
+                self.line = line;
+                let try_jump = self.emit(OpCode::Try(0));
+                self.current.try_depth += 1;
+                self.begin_scope();
+                for stmt in body {
+                    self.compile_stmt(stmt)?;
+                }
+                self.end_scope();
+                self.current.try_depth -= 1;
+                self.emit(OpCode::EndTry);
+                let end_jump = self.emit(OpCode::Jump(0));
+
+                // The VM unwinds the stack to its height at 'probeer' and
+                // pushes the caught value, which becomes the handler's local
+                let handler_start = self.current_offset();
+                self.current.chunk.patch_jump(try_jump, handler_start);
+                self.begin_scope();
+                self.add_local(name)?;
+                for stmt in handler {
+                    self.compile_stmt(stmt)?;
+                }
+                self.end_scope();
+
+                let end = self.current_offset();
+                self.current.chunk.patch_jump(end_jump, end);
+            }
             Stmt::Block(statements) => {
                 self.begin_scope();
                 for stmt in statements {
@@ -334,11 +442,14 @@ impl Compiler {
 
                 if let Some(expr) = value {
                     // Check for tail call optimization
-                    if let Expr::Call { callee, arguments } = expr {
-                        self.compile_tail_call(callee, arguments)?;
-                    } else {
-                        self.compile_expr(expr)?;
-                        self.emit(OpCode::Return);
+                    match expr {
+                        Expr::Call { callee, arguments, line } if self.current.try_depth == 0 => {
+                            self.compile_tail_call(callee, arguments, line)?;
+                        }
+                        expr => {
+                            self.compile_expr(expr)?;
+                            self.emit(OpCode::Return);
+                        }
                     }
                 } else {
                     let nil_idx = self.add_constant(Value::Nil);
@@ -363,11 +474,14 @@ impl Compiler {
                 self.emit(OpCode::Pop); // Pop condition
 
                 // Compile value and return
-                if let Expr::Call { callee, arguments } = value {
-                    self.compile_tail_call(callee, arguments)?;
-                } else {
-                    self.compile_expr(value)?;
-                    self.emit(OpCode::Return);
+                match value {
+                    Expr::Call { callee, arguments, line } if self.current.try_depth == 0 => {
+                        self.compile_tail_call(callee, arguments, line)?;
+                    }
+                    value => {
+                        self.compile_expr(value)?;
+                        self.emit(OpCode::Return);
+                    }
                 }
 
                 // Patch jump: come here if condition was false
@@ -377,22 +491,29 @@ impl Compiler {
 
                 // If there's an else value, return it
                 if let Some(else_expr) = else_value {
-                    if let Expr::Call { callee, arguments } = else_expr {
-                        self.compile_tail_call(callee, arguments)?;
-                    } else {
-                        self.compile_expr(else_expr)?;
-                        self.emit(OpCode::Return);
+                    match else_expr {
+                        Expr::Call { callee, arguments, line } if self.current.try_depth == 0 => {
+                            self.compile_tail_call(callee, arguments, line)?;
+                        }
+                        else_expr => {
+                            self.compile_expr(else_expr)?;
+                            self.emit(OpCode::Return);
+                        }
                     }
                 }
                 // If no else, execution continues to next statement
             }
             Stmt::TypeDecl { name, constructors } => {
+                self.register_type(&constructors);
+
                 // For each constructor, create a TypeConstructor value and define it as a global
-                for constructor in constructors {
+                for (tag, constructor) in constructors.into_iter().enumerate() {
                     let constructor_def = TypeConstructorDef {
                         type_name: name.clone(),
                         constructor_name: constructor.name.clone(),
+                        tag,
                         arity: constructor.fields.len(),
+                        field_names: Rc::new(constructor.fields),
                     };
 
                     let constructor_value = Value::TypeConstructor(Rc::new(constructor_def));
@@ -439,9 +560,11 @@ impl Compiler {
             }
             Expr::Unary { operator, right } => {
                 self.compile_expr(*right)?;
+                self.line = operator.line;
                 match operator.token_type {
                     TokenType::Minus => self.emit(OpCode::Negate),
                     TokenType::Bang => self.emit(OpCode::Not),
+                    TokenType::Tilde => self.emit(OpCode::BitNot),
                     _ => return Err("Onbekende unêre operator.".to_string()),
                 };
             }
@@ -475,11 +598,13 @@ impl Compiler {
                     self.compile_expr(*left)?;
                     self.compile_expr(*right)?;
 
+                    self.line = operator.line;
                     match operator.token_type {
                         TokenType::Plus => self.emit(OpCode::Add),
                         TokenType::Minus => self.emit(OpCode::Subtract),
                         TokenType::Star => self.emit(OpCode::Multiply),
                         TokenType::Slash => self.emit(OpCode::Divide),
+                        TokenType::TildeSlash => self.emit(OpCode::IntDivide),
                         TokenType::Percent => self.emit(OpCode::Modulo),
                         TokenType::EqualEqual => self.emit(OpCode::Equal),
                         TokenType::BangEqual => self.emit(OpCode::NotEqual),
@@ -487,21 +612,27 @@ impl Compiler {
                         TokenType::LessEqual => self.emit(OpCode::LessEqual),
                         TokenType::Greater => self.emit(OpCode::Greater),
                         TokenType::GreaterEqual => self.emit(OpCode::GreaterEqual),
+                        TokenType::Ampersand => self.emit(OpCode::BitAnd),
+                        TokenType::Pipe => self.emit(OpCode::BitOr),
+                        TokenType::Caret => self.emit(OpCode::BitXor),
+                        TokenType::LessLess => self.emit(OpCode::ShiftLeft),
+                        TokenType::GreaterGreater => self.emit(OpCode::ShiftRight),
                         _ => return Err("Onbekende binêre operator.".to_string()),
                     };
                 }
             },
-            Expr::Call { callee, arguments } => {
+            Expr::Call { callee, arguments, line } => {
                 // Compile the callee (the function to call)
                 self.compile_expr(*callee)?;
 
                 // Compile arguments
                 let arg_count = self.compile_arguments(arguments)?;
+                self.line = line;
                 self.emit(OpCode::Call(arg_count));
             }
-            Expr::Lambda { params, body } => {
+            Expr::Lambda { params, body, line } => {
                 // Compile lambda similar to a function
-                let (chunk, arity, upvalues) = self.compile_lambda(params, body)?;
+                let (chunk, arity, upvalues) = self.compile_lambda(params, body, line)?;
 
                 // Create function value
                 let function = Value::Function(Rc::new(Function {
@@ -529,13 +660,61 @@ impl Compiler {
                 // Create list from stack values
                 self.emit(OpCode::MakeList(count));
             }
-            Expr::Index { object, index } => {
+            Expr::Interpolation(parts) => {
+                // Empty literal pieces add nothing to the result
+                let parts: Vec<Expr> = parts
+                    .into_iter()
+                    .filter(|part| !matches!(part, Expr::Literal(Literal::String(s)) if s.is_empty()))
+                    .collect();
+                let count = parts.len();
+                for part in parts {
+                    self.compile_expr(part)?;
+                }
+                self.emit(OpCode::Concat(count));
+            }
+            Expr::Tuple(elements) => {
+                let count = elements.len();
+                for elem in elements {
+                    self.compile_expr(elem)?;
+                }
+                self.emit(OpCode::MakeTuple(count));
+            }
+            Expr::Dict(entries) => {
+                // Push key/value pairs in order
+                let count = entries.len();
+                for (key, value) in entries {
+                    self.compile_expr(key)?;
+                    self.compile_expr(value)?;
+                }
+                self.emit(OpCode::MakeDict(count));
+            }
+            Expr::Propagate { value, line } => {
+                if self.current.function_type == FunctionType::Script {
+                    return Err(format!("Kan '?' nie buite 'n funksie gebruik nie. (lyn {})", line));
+                }
+                self.compile_expr(*value)?;
+                self.line = line;
+                self.emit(OpCode::CheckFailure);
+                let success_jump = self.emit(OpCode::JumpIfFalse(0));
+                // Niks or Fout: return it from the function
+                self.emit(OpCode::Pop);
+                self.emit(OpCode::Return);
+
+                // Sommige or Ok: replace it with the value inside
+                let success = self.current_offset();
+                self.current.chunk.patch_jump(success_jump, success);
+                self.emit(OpCode::Pop);
+                self.emit(OpCode::GetFieldPop(0));
+            }
+            Expr::Index { object, index, line } => {
                 // Compile the object (list) and index
                 self.compile_expr(*object)?;
                 self.compile_expr(*index)?;
+                self.line = line;
                 self.emit(OpCode::GetIndex);
             }
-            Expr::Match { value, arms } => {
+            Expr::Match { value, arms, line } => {
+                self.line = line;
                 // Begin a scope for the entire match expression
                 self.begin_scope();
 
@@ -548,21 +727,21 @@ impl Compiler {
                 // Track jump addresses
                 let mut end_jumps = Vec::new();
 
-                for (i, arm) in arms.iter().enumerate() {
-                    let is_last = i == arms.len() - 1;
-
-                    // Get a copy of the scrutinee onto the stack
-                    self.emit(OpCode::GetLocal(scrutinee_slot));
-
+                for arm in arms {
                     // Begin a new scope for pattern bindings
                     self.begin_scope();
 
+                    // Reserve a slot for every binding up front, so that all
+                    // alternatives and failure paths agree on the stack layout
+                    let bindings = self.collect_pattern_bindings(&arm.pattern)?;
+                    self.reserve_pattern_bindings(&bindings)?;
+
                     // Compile pattern matching
-                    let bindings = self.collect_pattern_bindings(&arm.pattern);
-                    let fail_jump = self.compile_pattern(&arm.pattern, !is_last)?;
+                    let mut fail_jumps = Vec::new();
+                    self.compile_pattern(&arm.pattern, scrutinee_slot, &mut Vec::new(), &mut fail_jumps)?;
 
                     // Compile the body
-                    self.compile_expr((*arm.body).clone())?;
+                    self.compile_expr(*arm.body)?;
 
                     // Clean up: result is on top, bindings below, scrutinee at bottom
                     // Stack: [scrutinee, bindings..., result]
@@ -573,9 +752,14 @@ impl Compiler {
                     // Pop the result from top (it's saved in slot 0)
                     self.emit(OpCode::Pop);
                     // Pop each binding manually (can't use end_scope() - need precise stack control)
-                    for _ in 0..bindings {
-                        self.emit(OpCode::Pop);
-                        self.current.locals.pop();
+                    // Bindings captured by closures are moved to the heap first
+                    for _ in 0..bindings.len() {
+                        let local = self.current.locals.pop().unwrap();
+                        if local.is_captured {
+                            self.emit(OpCode::CloseUpvalue);
+                        } else {
+                            self.emit(OpCode::Pop);
+                        }
                     }
                     self.current.scope_depth -= 1;
                     // Stack is now [result] in the scrutinee slot position
@@ -583,20 +767,29 @@ impl Compiler {
                     // Jump to end after successful match
                     end_jumps.push(self.emit(OpCode::Jump(0)));
 
-                    // Patch the fail jump to come here (next arm)
-                    // When pattern fails, we need to clean up the stack:
-                    // - Pop the boolean from CheckConstructor (if it was a constructor pattern)
-                    // - Pop the scrutinee copy that we pushed for this arm
-                    if let Some(fail_addr) = fail_jump {
+                    // Patch the fail jumps to come here (next arm)
+                    // Every failing check leaves [bindings..., value, false] on the stack
+                    if !fail_jumps.is_empty() {
                         let next_arm = self.current_offset();
-                        self.current.chunk.patch_jump(fail_addr, next_arm);
-                        // Pop the boolean result from CheckConstructor or Equal
+                        for fail_addr in fail_jumps {
+                            self.current.chunk.patch_jump(fail_addr, next_arm);
+                        }
+                        // Pop the boolean result and the value that was checked
                         self.emit(OpCode::Pop);
-                        // Pop the scrutinee copy for this failed arm
                         self.emit(OpCode::Pop);
+                        // Pop the reserved binding slots for this failed arm
+                        for _ in 0..bindings.len() {
+                            self.emit(OpCode::Pop);
+                        }
                     }
                 }
 
+                // Falling through the last arm means no pattern matched
+                self.emit(OpCode::MatchFailed(format!(
+                    "Geen geval in pas-uitdrukking het gepas nie. (lyn {})",
+                    line
+                )));
+
                 // Patch all end jumps to come here
                 let end = self.current_offset();
                 for jump in end_jumps {
@@ -644,144 +837,309 @@ impl Compiler {
                 // Emit GetMember instruction
                 self.emit(OpCode::GetMember(member));
             }
+            Expr::NamedCall { callee, arguments } => {
+                self.compile_expr(*callee)?;
+                let mut names = Vec::new();
+                for (name, value) in arguments {
+                    self.compile_expr(value)?;
+                    names.push(name);
+                }
+                self.emit(OpCode::CallNamed(names));
+            }
+            Expr::RecordUpdate { object, fields } => {
+                self.compile_expr(*object)?;
+                let mut names = Vec::new();
+                for (name, value) in fields {
+                    self.compile_expr(value)?;
+                    names.push(name);
+                }
+                self.emit(OpCode::UpdateFields(names));
+            }
         }
 
         Ok(())
     }
 
-    /// Count how many stack slots a pattern will occupy (including hidden locals)
-    fn collect_pattern_bindings(&self, pattern: &Pattern) -> usize {
+    /// Collect the names a pattern binds, in the order their slots are reserved.
+    /// Every alternative of an or-pattern must bind the same names; the first
+    /// alternative decides the order.
+    fn collect_pattern_bindings(&self, pattern: &Pattern) -> Result<Vec<String>, String> {
         match pattern {
-            Pattern::Wildcard => 0,
-            Pattern::Variable(_) => 1,
-            Pattern::Literal(_) => 0,
-            Pattern::Constructor { fields, .. } => {
-                // Count the hidden $ctor local plus all field bindings
-                let field_bindings: usize = fields
-                    .iter()
-                    .map(|p| self.collect_pattern_bindings(p))
-                    .sum();
-                if fields.is_empty() {
-                    0 // No hidden local for zero-field constructors
-                } else {
-                    1 + field_bindings // 1 for $ctor + field bindings
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::Range { .. } => Ok(Vec::new()),
+            Pattern::Variable(name) => Ok(vec![name.clone()]),
+            Pattern::Constructor { fields, .. } | Pattern::List(fields) | Pattern::Tuple(fields) => {
+                let mut names = Vec::new();
+                for field in fields {
+                    names.extend(self.collect_pattern_bindings(field)?);
                 }
+                Ok(names)
+            }
+            Pattern::Binding { name, pattern } => {
+                let mut names = vec![name.clone()];
+                names.extend(self.collect_pattern_bindings(pattern)?);
+                Ok(names)
+            }
+            Pattern::Or(alternatives) => {
+                let names = self.collect_pattern_bindings(&alternatives[0])?;
+                let mut expected = names.clone();
+                expected.sort();
+
+                for alternative in &alternatives[1..] {
+                    let mut other = self.collect_pattern_bindings(alternative)?;
+                    other.sort();
+                    if other != expected {
+                        return Err(format!(
+                            "Alle alternatiewe van 'n of-patroon moet dieselfde name bind: [{}] teenoor [{}].",
+                            expected.join(", "),
+                            other.join(", ")
+                        ));
+                    }
+                }
+
+                Ok(names)
             }
         }
     }
 
-    /// Compile a pattern match check. Returns jump address if pattern can fail.
+    /// Compile a pattern match check against the value found at `path` inside
+    /// the scrutinee (a list of field indices, empty for the scrutinee itself).
     ///
-    /// This function expects the value to match on top of the stack.
-    /// After matching:
-    /// - For Variable: the value remains on stack as a new local
-    /// - For Wildcard: the value is popped
-    /// - For Literal: the value is popped
-    /// - For Constructor: the constructor is popped, but field bindings remain
+    /// The stack is left unchanged on success; bound values are stored in the
+    /// binding slots reserved by the caller. Every check that can fail emits a
+    /// `JumpIfFalse` into `fail_jumps`, taken with `[value, false]` on the stack.
     fn compile_pattern(
         &mut self,
         pattern: &Pattern,
-        can_fail: bool,
-    ) -> Result<Option<usize>, String> {
+        scrutinee_slot: usize,
+        path: &mut Vec<usize>,
+        fail_jumps: &mut Vec<usize>,
+    ) -> Result<(), String> {
         match pattern {
             Pattern::Wildcard => {
-                // Always matches, pop the value
-                self.emit(OpCode::Pop);
-                Ok(None)
+                // Always matches, nothing to do
             }
             Pattern::Variable(name) => {
-                // Bind the value to a local constant
-                // The value is on top of stack and becomes the local's storage
-                self.add_local(name.clone())?;
-                Ok(None)
+                self.compile_pattern_bind(name, scrutinee_slot, path);
             }
             Pattern::Literal(lit) => {
-                // Duplicate scrutinee so we don't consume it during comparison
+                self.emit_pattern_value(scrutinee_slot, path);
+                // Duplicate the value so the failure path has a uniform layout
                 self.emit(OpCode::Dup);
-                // Compare with literal
                 let const_value = self.literal_to_value(lit);
                 let const_idx = self.add_constant(const_value);
                 self.emit(OpCode::Constant(const_idx));
                 self.emit(OpCode::Equal);
-                // Stack now has: [..., scrutinee, bool]
-
-                let fail_jump = if can_fail {
-                    Some(self.emit(OpCode::JumpIfFalse(0)))
-                } else {
-                    None
-                };
-                self.emit(OpCode::Pop); // Pop the boolean result
-                self.emit(OpCode::Pop); // Pop the scrutinee (literal patterns don't bind)
-                Ok(fail_jump)
+                self.emit_pattern_check(fail_jumps);
+            }
+            Pattern::Range { start, end } => {
+                self.emit_pattern_value(scrutinee_slot, path);
+                let (start, end) = (self.literal_to_value(start), self.literal_to_value(end));
+                self.emit(OpCode::CheckRange(start, end));
+                self.emit_pattern_check(fail_jumps);
             }
             Pattern::Constructor { name, fields } => {
                 // Check if value is this constructor with correct arity
+                self.emit_pattern_value(scrutinee_slot, path);
                 self.emit(OpCode::CheckConstructor(name.clone(), fields.len()));
+                self.emit_pattern_check(fail_jumps);
 
-                let fail_jump = if can_fail {
-                    Some(self.emit(OpCode::JumpIfFalse(0)))
-                } else {
-                    None
-                };
-                self.emit(OpCode::Pop); // Pop the boolean result
+                // Recursively match each field
+                for (i, field_pattern) in fields.iter().enumerate() {
+                    path.push(i);
+                    self.compile_pattern(field_pattern, scrutinee_slot, path, fail_jumps)?;
+                    path.pop();
+                }
+            }
+            Pattern::List(elements) => {
+                // Check that the value is a list of exactly this length
+                self.emit_pattern_value(scrutinee_slot, path);
+                self.emit(OpCode::CheckList(elements.len()));
+                self.emit_pattern_check(fail_jumps);
+
+                for (i, element_pattern) in elements.iter().enumerate() {
+                    path.push(i);
+                    self.compile_pattern(element_pattern, scrutinee_slot, path, fail_jumps)?;
+                    path.pop();
+                }
+            }
+            Pattern::Tuple(elements) => {
+                // Check that the value is a tuple of exactly this size
+                self.emit_pattern_value(scrutinee_slot, path);
+                self.emit(OpCode::CheckTuple(elements.len()));
+                self.emit_pattern_check(fail_jumps);
+
+                for (i, element_pattern) in elements.iter().enumerate() {
+                    path.push(i);
+                    self.compile_pattern(element_pattern, scrutinee_slot, path, fail_jumps)?;
+                    path.pop();
+                }
+            }
+            Pattern::Binding { name, pattern } => {
+                self.compile_pattern_bind(name, scrutinee_slot, path);
+                self.compile_pattern(pattern, scrutinee_slot, path, fail_jumps)?;
+            }
+            Pattern::Or(alternatives) => {
+                let mut matched_jumps = Vec::new();
+                let last = alternatives.len() - 1;
+
+                for (i, alternative) in alternatives.iter().enumerate() {
+                    if i == last {
+                        // Failure of the last alternative fails the whole pattern
+                        self.compile_pattern(alternative, scrutinee_slot, path, fail_jumps)?;
+                    } else {
+                        let mut alternative_fails = Vec::new();
+                        self.compile_pattern(alternative, scrutinee_slot, path, &mut alternative_fails)?;
+                        matched_jumps.push(self.emit(OpCode::Jump(0)));
+
+                        // Clean up the failed check and try the next alternative
+                        let next_alternative = self.current_offset();
+                        for fail_addr in alternative_fails {
+                            self.current.chunk.patch_jump(fail_addr, next_alternative);
+                        }
+                        self.emit(OpCode::Pop);
+                        self.emit(OpCode::Pop);
+                    }
+                }
+
+                let matched = self.current_offset();
+                for jump in matched_jumps {
+                    self.current.chunk.patch_jump(jump, matched);
+                }
+            }
+        }
 
-                let num_fields = fields.len();
+        Ok(())
+    }
 
-                if num_fields == 0 {
-                    // No fields, just pop the ADT
-                    self.emit(OpCode::Pop);
-                } else {
-                    // For multi-field constructors, save the ADT as a hidden local
-                    // This ensures field extractions use correct stack indexing
-                    self.add_local(String::from(CTOR_HIDDEN_LOCAL))?;
-                    let ctor_slot = self.resolve_local(CTOR_HIDDEN_LOCAL).unwrap();
-
-                    // Extract each field value and process its pattern
-                    for (i, field_pattern) in fields.iter().enumerate() {
-                        // Get the ADT from the hidden local
-                        self.emit(OpCode::GetLocal(ctor_slot));
-                        // Get the field value (leaves ADT copy on stack, pushes field)
-                        self.emit(OpCode::GetFieldPop(i));
-
-                        // Recursively compile the field pattern
-                        self.compile_pattern(field_pattern, false)?;
-                    }
+    /// Push a placeholder local for each pattern binding
+    fn reserve_pattern_bindings(&mut self, bindings: &[String]) -> Result<(), String> {
+        let nil_idx = self.add_constant(Value::Nil);
+        for name in bindings {
+            self.emit(OpCode::Constant(nil_idx));
+            self.add_local(name.clone())?;
+        }
+        Ok(())
+    }
 
-                    // Pop the hidden ADT local
-                    // The field bindings are now proper locals above it
-                    // We need to remove $ctor from locals but keep the field bindings
-                    // Actually, just leave it - the scope management will handle it
-                    // But we do need to pop it from the stack eventually...
-                    // Actually no - GetLocal doesn't remove from stack, it copies.
-                    // The ADT is still sitting in its slot. We need to remove it.
+    /// Destructure the value in `value_slot`, leaving the bindings as new locals.
+    /// If the pattern does not match, a runtime error is raised.
+    fn compile_destructure(
+        &mut self,
+        pattern: &Pattern,
+        value_slot: usize,
+        line: usize,
+    ) -> Result<Vec<String>, String> {
+        let bindings = self.collect_pattern_bindings(pattern)?;
+        self.reserve_pattern_bindings(&bindings)?;
+
+        let mut fail_jumps = Vec::new();
+        self.compile_pattern(pattern, value_slot, &mut Vec::new(), &mut fail_jumps)?;
+
+        if !fail_jumps.is_empty() {
+            let matched_jump = self.emit(OpCode::Jump(0));
+            let fail = self.current_offset();
+            for fail_addr in fail_jumps {
+                self.current.chunk.patch_jump(fail_addr, fail);
+            }
+            self.emit(OpCode::MatchFailed(format!(
+                "Patroon het nie gepas nie. (lyn {})",
+                line
+            )));
+            let matched = self.current_offset();
+            self.current.chunk.patch_jump(matched_jump, matched);
+        }
 
-                    // The $ctor local is still at ctor_slot on the stack
-                    // We need to pop it but keep the field values above it
-                    // This requires swapping - but we don't have that.
-                    // Alternative: leave it and let scope cleanup handle it
-                    // But that messes up the binding count.
+        Ok(bindings)
+    }
 
-                    // Actually, let's track that we added this hidden local
-                    // and handle it in the cleanup. For now, just keep it.
-                }
+    /// Remember how many constructors the type of each constructor has
+    fn register_type(&mut self, constructors: &[TypeConstructor]) {
+        for constructor in constructors {
+            self.type_constructors
+                .insert(constructor.name.clone(), constructors.len());
+        }
+    }
 
-                Ok(fail_jump)
+    /// A pattern is irrefutable if it matches every value of the expected shape:
+    /// constructor patterns only qualify when their type has a single constructor.
+    fn is_irrefutable(&self, pattern: &Pattern) -> bool {
+        match pattern {
+            Pattern::Wildcard | Pattern::Variable(_) => true,
+            Pattern::Literal(_) | Pattern::Range { .. } | Pattern::List(_) => false,
+            Pattern::Constructor { name, fields } => {
+                self.type_constructors.get(name) == Some(&1)
+                    && fields.iter().all(|field| self.is_irrefutable(field))
             }
+            Pattern::Tuple(elements) => elements.iter().all(|element| self.is_irrefutable(element)),
+            Pattern::Or(alternatives) => alternatives.iter().any(|alt| self.is_irrefutable(alt)),
+            Pattern::Binding { pattern, .. } => self.is_irrefutable(pattern),
         }
     }
 
+    /// Push the value at `path` inside the scrutinee onto the stack
+    fn emit_pattern_value(&mut self, scrutinee_slot: usize, path: &[usize]) {
+        self.emit(OpCode::GetLocal(scrutinee_slot));
+        for index in path {
+            self.emit(OpCode::GetFieldPop(*index));
+        }
+    }
+
+    /// Store the value at `path` in the reserved slot for `name`
+    fn compile_pattern_bind(&mut self, name: &str, scrutinee_slot: usize, path: &[usize]) {
+        let slot = self
+            .resolve_local(name)
+            .expect("pattern binding slots are reserved before matching");
+        self.emit_pattern_value(scrutinee_slot, path);
+        self.emit(OpCode::SetLocal(slot));
+        self.emit(OpCode::Pop);
+    }
+
+    /// Branch to the failure path if the check on top of the stack is false.
+    /// Expects `[value, bool]` and leaves the stack unchanged on success.
+    fn emit_pattern_check(&mut self, fail_jumps: &mut Vec<usize>) {
+        fail_jumps.push(self.emit(OpCode::JumpIfFalse(0)));
+        self.emit(OpCode::Pop); // Pop the boolean result
+        self.emit(OpCode::Pop); // Pop the checked value
+    }
+
     fn compile_lambda(
         &mut self,
-        params: Vec<String>,
+        params: Vec<Pattern>,
         body: LambdaBody,
+        line: usize,
     ) -> Result<(Rc<Chunk>, usize, Vec<UpvalueDescriptor>), String> {
-        self.compile_callable(String::from("<lambda>"), params, |compiler| {
+        // Pattern parameters are received in hidden locals and destructured on entry
+        let mut names = Vec::with_capacity(params.len());
+        let mut destructured = Vec::new();
+        for (i, param) in params.into_iter().enumerate() {
+            match param {
+                Pattern::Variable(name) => names.push(name),
+                pattern => {
+                    if !self.is_irrefutable(&pattern) {
+                        return Err(format!(
+                            "Parameter-patroon kan misluk; slegs tipes met een konstruktor kan as parameter gedestruktureer word. Gebruik 'pas' in die liggaam. (lyn {})",
+                            line
+                        ));
+                    }
+                    let hidden = format!("{}{}", PARAM_HIDDEN_LOCAL, i);
+                    names.push(hidden.clone());
+                    destructured.push((hidden, pattern));
+                }
+            }
+        }
+
+        self.compile_callable(String::from("<lambda>"), names, |compiler| {
+            for (hidden, pattern) in destructured {
+                let slot = compiler.resolve_local(&hidden).unwrap();
+                compiler.compile_destructure(&pattern, slot, line)?;
+            }
+
             match body {
                 LambdaBody::Expr(expr) => {
                     // Single expression - implicit return
                     // Check for tail call optimization
-                    if let Expr::Call { callee, arguments } = *expr {
-                        compiler.compile_tail_call(callee, arguments)?;
+                    if let Expr::Call { callee, arguments, line } = *expr {
+                        compiler.compile_tail_call(callee, arguments, line)?;
                     } else {
                         compiler.compile_expr(*expr)?;
                         compiler.emit(OpCode::Return);
@@ -803,7 +1161,7 @@ impl Compiler {
     }
 
     fn emit(&mut self, op: OpCode) -> usize {
-        self.current.chunk.write(op)
+        self.current.chunk.write(op, self.line)
     }
 
     fn add_constant(&mut self, value: Value) -> usize {
@@ -817,6 +1175,8 @@ impl Compiler {
     fn literal_to_value(&self, lit: &Literal) -> Value {
         match lit {
             Literal::Number(n) => Value::Number(*n),
+            Literal::Integer(i) => Value::Integer(*i),
+            Literal::BigInt(n) => numeric::from_bigint(n.clone()),
             Literal::Boolean(b) => Value::Boolean(*b),
             Literal::String(s) => Value::String(Rc::new(s.clone())),
             Literal::Nil => Value::Nil,
@@ -842,9 +1202,10 @@ impl Compiler {
         Ok(arg_count)
     }
 
-    fn compile_tail_call(&mut self, callee: Box<Expr>, arguments: Vec<Expr>) -> Result<(), String> {
+    fn compile_tail_call(&mut self, callee: Box<Expr>, arguments: Vec<Expr>, line: usize) -> Result<(), String> {
         self.compile_expr(*callee)?;
         let arg_count = self.compile_arguments(arguments)?;
+        self.line = line;
         self.emit(OpCode::TailCall(arg_count));
         Ok(())
     }
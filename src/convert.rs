@@ -0,0 +1,185 @@
+//! Conversions between Arkaan values and Rust types, for embedding hosts.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::value::{Dictionary, Value};
+
+/// Converts a Rust value into an Arkaan value
+pub trait IntoValue {
+    fn into_value(self) -> Value;
+}
+
+/// Converts an Arkaan value into a Rust value, failing on a type mismatch
+pub trait FromValue: Sized {
+    fn from_value(value: &Value) -> Result<Self, String>;
+}
+
+fn expected(kind: &str, value: &Value) -> String {
+    format!("Verwag {}, maar het {} gekry.", kind, value.type_name())
+}
+
+impl IntoValue for Value {
+    fn into_value(self) -> Value {
+        self
+    }
+}
+
+impl IntoValue for () {
+    fn into_value(self) -> Value {
+        Value::Nil
+    }
+}
+
+impl IntoValue for bool {
+    fn into_value(self) -> Value {
+        Value::Boolean(self)
+    }
+}
+
+impl IntoValue for i64 {
+    fn into_value(self) -> Value {
+        Value::Integer(self)
+    }
+}
+
+impl IntoValue for i32 {
+    fn into_value(self) -> Value {
+        Value::Integer(self as i64)
+    }
+}
+
+impl IntoValue for f64 {
+    fn into_value(self) -> Value {
+        Value::Number(self)
+    }
+}
+
+impl IntoValue for String {
+    fn into_value(self) -> Value {
+        Value::String(Rc::new(self))
+    }
+}
+
+impl IntoValue for &str {
+    fn into_value(self) -> Value {
+        Value::String(Rc::new(self.to_string()))
+    }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+    fn into_value(self) -> Value {
+        match self {
+            Some(value) => value.into_value(),
+            None => Value::Nil,
+        }
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Value {
+        Value::List(Rc::new(self.into_iter().map(IntoValue::into_value).collect()))
+    }
+}
+
+/// Keys are inserted in sorted order so the dictionary prints the same every run
+impl<T: IntoValue> IntoValue for HashMap<String, T> {
+    fn into_value(self) -> Value {
+        let mut entries: Vec<(String, T)> = self.into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut dict = Dictionary::new();
+        for (key, value) in entries {
+            // String keys are always hashable
+            let _ = dict.insert(key.into_value(), value.into_value());
+        }
+        Value::Woordeboek(Rc::new(dict))
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        Ok(value.clone())
+    }
+}
+
+impl FromValue for () {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Nil => Ok(()),
+            _ => Err(expected("nil", value)),
+        }
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Boolean(b) => Ok(*b),
+            _ => Err(expected("'n boolean", value)),
+        }
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Integer(n) => Ok(*n),
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => Ok(*n as i64),
+            Value::BigInt(n) => n
+                .to_i64()
+                .ok_or_else(|| format!("Getal {} is te groot vir i64.", value)),
+            _ => Err(expected("'n heelgetal", value)),
+        }
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Number(n) => Ok(*n),
+            Value::Integer(n) => Ok(*n as f64),
+            Value::BigInt(n) => Ok(n.to_f64()),
+            Value::Rational(r) => Ok(r.to_f64()),
+            _ => Err(expected("'n nommer", value)),
+        }
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::String(s) => Ok(s.as_ref().clone()),
+            _ => Err(expected("'n string", value)),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Nil => Ok(None),
+            _ => T::from_value(value).map(Some),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::List(items) | Value::Tuple(items) => items.iter().map(T::from_value).collect(),
+            _ => Err(expected("'n lys", value)),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for HashMap<String, T> {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Woordeboek(dict) => dict
+                .entries()
+                .map(|(key, value)| Ok((String::from_value(key)?, T::from_value(value)?)))
+                .collect(),
+            _ => Err(expected("'n woordeboek", value)),
+        }
+    }
+}
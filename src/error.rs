@@ -0,0 +1,96 @@
+//! Errors the host sees, and the kinds of built-in runtime errors, reported
+//! as the 'soort' of a caught Uitsondering.
+
+use std::fmt;
+
+use crate::value::Value;
+
+/// Why compiling or running Arkaan code for the host failed
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The source doesn't lex, parse or compile
+    Syntax(String),
+    /// An uncaught built-in error, or a raised Uitsondering of a built-in kind
+    Runtime { kind: ErrorKind, message: String, line: usize },
+    /// Any other value a script raised with 'gooi' and nobody caught
+    Thrown { value: Value, message: String, line: usize },
+    /// A sandbox limit stopped the run; scripts can't catch these
+    Limit(String),
+    /// The script called `verlaat` with this code
+    Exit(i32),
+}
+
+impl Error {
+    /// A runtime error raised by the host side rather than the script
+    pub(crate) fn runtime(kind: ErrorKind, message: String) -> Self {
+        Error::Runtime { kind, message, line: 0 }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Syntax(message) | Error::Limit(message) => write!(f, "{}", message),
+            Error::Runtime { message, .. } | Error::Thrown { message, .. } => write!(f, "{}", message),
+            Error::Exit(code) => write!(f, "verlaat({})", code),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// What went wrong in a built-in error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    DivisionByZero,
+    Index,
+    EmptyList,
+    Undefined,
+    Arguments,
+    Match,
+    Module,
+    InputOutput,
+    Type,
+    Value,
+    Runtime,
+}
+
+impl ErrorKind {
+    /// The name scripts see in 'soort'
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::DivisionByZero => "deling_deur_nul",
+            ErrorKind::Index => "indeks",
+            ErrorKind::EmptyList => "leë_lys",
+            ErrorKind::Undefined => "ongedefinieer",
+            ErrorKind::Arguments => "argumente",
+            ErrorKind::Match => "pas",
+            ErrorKind::Module => "module",
+            ErrorKind::InputOutput => "invoer_uitvoer",
+            ErrorKind::Type => "tipe",
+            ErrorKind::Value => "waarde",
+            ErrorKind::Runtime => "looptyd",
+        }
+    }
+
+    /// The kind a 'soort' names, if it is a built-in one
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "deling_deur_nul" => Some(ErrorKind::DivisionByZero),
+            "indeks" => Some(ErrorKind::Index),
+            "leë_lys" => Some(ErrorKind::EmptyList),
+            "ongedefinieer" => Some(ErrorKind::Undefined),
+            "argumente" => Some(ErrorKind::Arguments),
+            "pas" => Some(ErrorKind::Match),
+            "module" => Some(ErrorKind::Module),
+            "invoer_uitvoer" => Some(ErrorKind::InputOutput),
+            "tipe" => Some(ErrorKind::Type),
+            "waarde" => Some(ErrorKind::Value),
+            "looptyd" => Some(ErrorKind::Runtime),
+            _ => None,
+        }
+    }
+}
+
+/// A built-in error raised outside the VM, before it records the kind
+pub type Failure = (ErrorKind, String);
@@ -0,0 +1,186 @@
+//! Embedding API: a long-lived interpreter whose globals persist between calls.
+
+use std::cell::RefCell;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::bytecode::Chunk;
+use crate::compiler::Compiler;
+use crate::convert::{FromValue, IntoValue};
+use crate::error::{Error, ErrorKind};
+use crate::lexer::Lexer;
+use crate::limits::Limits;
+use crate::parser::Parser;
+use crate::value::{Arity, NativeFunction, Value};
+use crate::vm::VM;
+
+pub struct Interpreter {
+    vm: VM,
+}
+
+/// Source code compiled by `Interpreter::compile`, ready to `run`
+pub struct Program {
+    chunk: Chunk,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Interpreter {
+            vm: VM::new(Chunk::new(), Vec::new()),
+        }
+    }
+
+    /// An interpreter for untrusted code; see `Limits`
+    pub fn with_limits(limits: Limits) -> Self {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_limits(limits);
+        interpreter
+    }
+
+    /// Limits apply to each `eval`, `run_file` and `call` separately
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.vm.set_limits(limits);
+    }
+
+    /// Run source code and return the value of its final expression statement
+    /// (nil if it ends with anything else). Definitions stay visible to later calls.
+    pub fn eval(&mut self, source: &str) -> Result<Value, Error> {
+        let program = self.compile(source)?;
+        self.run(&program)
+    }
+
+    /// Parse and compile source without running it; every error is an `Error::Syntax`
+    pub fn compile(&self, source: &str) -> Result<Program, Error> {
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.scan_tokens().map_err(Error::Syntax)?;
+
+        let mut parser = Parser::new(tokens);
+        let statements = parser.parse().map_err(Error::Syntax)?;
+
+        let mut compiler = Compiler::new();
+        let (chunk, _functions) = compiler.compile_eval(statements).map_err(Error::Syntax)?;
+        Ok(Program { chunk })
+    }
+
+    /// Run a compiled program, like `eval`
+    pub fn run(&mut self, program: &Program) -> Result<Value, Error> {
+        self.vm.execute(&program.chunk)
+    }
+
+    /// Run a script file; imports in it resolve relative to the file's directory
+    pub fn run_file(&mut self, path: impl AsRef<Path>) -> Result<Value, Error> {
+        let source = fs::read_to_string(path.as_ref())
+            .map_err(|e| Error::runtime(ErrorKind::InputOutput, format!("Kon nie lêer lees nie: {}", e)))?;
+        self.eval_script(&source, path)
+    }
+
+    /// Run source as if it had been read from `path`, for relative imports
+    pub fn eval_script(&mut self, source: &str, path: impl AsRef<Path>) -> Result<Value, Error> {
+        self.set_script_path(path);
+        self.eval(source)
+    }
+
+    /// Resolve imports and 'lêer' paths relative to this script from now on
+    pub fn set_script_path(&mut self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let file_path: PathBuf = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.vm.set_current_file(file_path);
+    }
+
+    /// The list `argumente()` returns
+    pub fn set_arguments(&mut self, arguments: Vec<String>) {
+        self.vm.set_arguments(arguments);
+    }
+
+    /// Send `druk` and `skryf` output somewhere other than stdout
+    pub fn set_output(&mut self, output: impl Write + 'static) {
+        self.vm.set_output(Box::new(output));
+    }
+
+    /// Send `druk_fout` output somewhere other than stderr
+    pub fn set_error_output(&mut self, error_output: impl Write + 'static) {
+        self.vm.set_error_output(Box::new(error_output));
+    }
+
+    /// Read `lees_lyn` and `lees_alles` input from somewhere other than stdin
+    pub fn set_input(&mut self, input: impl BufRead + 'static) {
+        self.vm.set_input(Box::new(input));
+    }
+
+    pub fn set_global(&mut self, name: &str, value: impl IntoValue) {
+        self.vm.set_global(name, value.into_value());
+    }
+
+    pub fn get_global(&self, name: &str) -> Option<Value> {
+        self.vm.get_global(name).cloned()
+    }
+
+    /// Read a global and convert it to a Rust type
+    pub fn get<T: FromValue>(&self, name: &str) -> Result<T, Error> {
+        match self.vm.get_global(name) {
+            Some(value) => T::from_value(value).map_err(|e| Error::runtime(ErrorKind::Type, e)),
+            None => Err(Error::runtime(ErrorKind::Undefined, format!("Ongedefinieerde veranderlike: '{}'", name))),
+        }
+    }
+
+    /// Call a global Arkaan function (or native) by name
+    pub fn call(&mut self, fn_name: &str, args: Vec<Value>) -> Result<Value, Error> {
+        let callee = self.vm.get_global(fn_name).cloned().ok_or_else(|| {
+            Error::runtime(ErrorKind::Undefined, format!("Ongedefinieerde veranderlike: '{}'", fn_name))
+        })?;
+        self.vm.call(callee, args)
+    }
+
+    /// Make a Rust closure callable from Arkaan under the given name
+    pub fn register_fn<F>(&mut self, name: &str, arity: Arity, func: F)
+    where
+        F: Fn(&[Value]) -> Result<Value, String> + 'static,
+    {
+        self.vm.define_native(NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Rc::new(move |_, args| func(args)),
+        });
+    }
+}
+
+/// An in-memory writer whose clones share one buffer, so output handed to an
+/// `Interpreter` can still be read afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct SharedBuffer {
+    bytes: Rc<RefCell<Vec<u8>>>,
+}
+
+impl SharedBuffer {
+    pub fn new() -> Self {
+        SharedBuffer::default()
+    }
+
+    /// Everything written so far, as text
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.bytes.borrow()).into_owned()
+    }
+
+    pub fn clear(&self) {
+        self.bytes.borrow_mut().clear();
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bytes.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
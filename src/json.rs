@@ -0,0 +1,195 @@
+//! JSON <-> Arkaan values. Objects become dictionaries (keys in document
+//! order), arrays lists and null nil. Integers that fit stay exact; other
+//! numbers are floats. Going the other way, tuples and sets are written as
+//! arrays, big integers digit for digit, and every ADT value as an object
+//! with one key, its constructor, holding an object of its fields.
+
+use std::fmt;
+use std::rc::Rc;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq, Serializer};
+use serde_json::error::Category;
+use serde_json::value::RawValue;
+
+use crate::bignum::BigInt;
+use crate::numeric;
+use crate::value::{Dictionary, Value};
+
+/// Parse a JSON document into a value
+pub fn parse(text: &str) -> Result<Value, String> {
+    let mut deserializer = serde_json::Deserializer::from_str(text);
+    let parsed = FromJson::deserialize(&mut deserializer).and_then(|value| {
+        deserializer.end()?;
+        Ok(value.0)
+    });
+    parsed.map_err(|e| {
+        let problem = match e.classify() {
+            Category::Eof => "onverwagte einde van invoer".to_string(),
+            Category::Syntax => "ongeldige sintaks".to_string(),
+            Category::Data | Category::Io => e.to_string(),
+        };
+        format!(
+            "json_lees() kan nie JSON lees nie: {} op lyn {}, kolom {}.",
+            problem,
+            e.line(),
+            e.column()
+        )
+    })
+}
+
+/// Write a value as JSON, indented when `pretty` is set
+pub fn stringify(value: &Value, pretty: bool) -> Result<String, String> {
+    let written = if pretty {
+        serde_json::to_string_pretty(&ToJson(value))
+    } else {
+        serde_json::to_string(&ToJson(value))
+    };
+    written.map_err(|e| format!("json_skryf() {}.", e))
+}
+
+struct FromJson(Value);
+
+impl<'de> Deserialize<'de> for FromJson {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(JsonVisitor).map(FromJson)
+    }
+}
+
+struct JsonVisitor;
+
+impl<'de> Visitor<'de> for JsonVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "'n JSON waarde")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Nil)
+    }
+
+    fn visit_bool<E: de::Error>(self, b: bool) -> Result<Value, E> {
+        Ok(Value::Boolean(b))
+    }
+
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<Value, E> {
+        Ok(Value::Integer(n))
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<Value, E> {
+        match i64::try_from(n) {
+            Ok(n) => Ok(Value::Integer(n)),
+            // Above i64::MAX: build it from halves
+            Err(_) => {
+                let half = BigInt::from_i64((n >> 1) as i64).shl(1);
+                Ok(numeric::from_bigint(half.add(&BigInt::from_i64((n & 1) as i64))))
+            }
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, n: f64) -> Result<Value, E> {
+        Ok(Value::Number(n))
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Value, E> {
+        Ok(Value::String(Rc::new(s.to_string())))
+    }
+
+    fn visit_string<E: de::Error>(self, s: String) -> Result<Value, E> {
+        Ok(Value::String(Rc::new(s)))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(FromJson(item)) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Value::List(Rc::new(items)))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut dict = Dictionary::new();
+        while let Some((key, FromJson(value))) = map.next_entry::<String, FromJson>()? {
+            dict.insert(Value::String(Rc::new(key)), value).map_err(de::Error::custom)?;
+        }
+        Ok(Value::Woordeboek(Rc::new(dict)))
+    }
+}
+
+struct ToJson<'a>(&'a Value);
+
+impl Serialize for ToJson<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Nil => serializer.serialize_unit(),
+            Value::Boolean(b) => serializer.serialize_bool(*b),
+            Value::Integer(n) => serializer.serialize_i64(*n),
+            Value::Number(n) if !n.is_finite() => {
+                Err(ser::Error::custom(format!("kan nie {} as JSON skryf nie", n)))
+            }
+            Value::Number(n) => serializer.serialize_f64(*n),
+            // A float would lose digits, so write the number as it prints
+            Value::BigInt(n) => RawValue::from_string(n.to_string())
+                .map_err(ser::Error::custom)?
+                .serialize(serializer),
+            Value::Rational(r) => Err(ser::Error::custom(format!(
+                "kan nie die breuk {} presies as JSON skryf nie; gebruik eers desimaal()",
+                r
+            ))),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::List(items) | Value::Tuple(items) => serialize_items(serializer, items),
+            Value::Versameling(set) => serialize_items(serializer, set.items()),
+            Value::Woordeboek(dict) => {
+                let mut map = serializer.serialize_map(Some(dict.len()))?;
+                for (key, value) in dict.entries() {
+                    match key {
+                        Value::String(key) => map.serialize_entry(key.as_str(), &ToJson(value))?,
+                        _ => {
+                            return Err(ser::Error::custom(format!(
+                                "kan slegs string sleutels as JSON skryf, nie {} nie",
+                                key.type_name()
+                            )))
+                        }
+                    }
+                }
+                map.end()
+            }
+            Value::Adt(adt) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(&adt.constructor_name, &Fields(&adt.field_names, &adt.fields))?;
+                map.end()
+            }
+            Value::TypeConstructor(tc) if tc.arity == 0 => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(&tc.constructor_name, &Fields(&[], &[]))?;
+                map.end()
+            }
+            value => Err(ser::Error::custom(format!(
+                "kan nie 'n {} as JSON skryf nie",
+                value.type_name()
+            ))),
+        }
+    }
+}
+
+/// The fields of an ADT value, as an object keyed by field name
+struct Fields<'a>(&'a [String], &'a [Value]);
+
+impl Serialize for Fields<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.1.len()))?;
+        for (name, value) in self.0.iter().zip(self.1.iter()) {
+            map.serialize_entry(name, &ToJson(value))?;
+        }
+        map.end()
+    }
+}
+
+fn serialize_items<S: Serializer>(serializer: S, items: &[Value]) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(Some(items.len()))?;
+    for item in items {
+        seq.serialize_element(&ToJson(item))?;
+    }
+    seq.end()
+}
@@ -1,3 +1,4 @@
+use crate::bignum::BigInt;
 use crate::token::{Token, TokenType};
 
 pub struct Lexer {
@@ -6,6 +7,18 @@ pub struct Lexer {
     start: usize,
     current: usize,
     line: usize,
+    /// Open string interpolations, innermost last
+    interpolation: Vec<Interpolation>,
+}
+
+/// An `{expr}` inside a string literal that is still being scanned
+struct Interpolation {
+    /// Braces opened inside the expression and not yet closed
+    depth: usize,
+    /// Line where the string started
+    line: usize,
+    /// Indentation stripped from each line of a triple-quoted string
+    indent: Option<usize>,
 }
 
 impl Lexer {
@@ -16,6 +29,7 @@ impl Lexer {
             start: 0,
             current: 0,
             line: 1,
+            interpolation: Vec::new(),
         }
     }
 
@@ -25,6 +39,10 @@ impl Lexer {
             self.scan_token()?;
         }
 
+        if let Some(open) = self.interpolation.last() {
+            return Err(format!("Onbeëindigde string op lyn {}", open.line));
+        }
+
         self.tokens.push(Token::new(TokenType::Eof, String::new(), self.line));
         Ok(self.tokens.clone())
     }
@@ -35,12 +53,36 @@ impl Lexer {
         match c {
             '(' => self.add_token(TokenType::LeftParen),
             ')' => self.add_token(TokenType::RightParen),
-            '{' => self.add_token(TokenType::LeftBrace),
-            '}' => self.add_token(TokenType::RightBrace),
+            '{' => {
+                if let Some(open) = self.interpolation.last_mut() {
+                    open.depth += 1;
+                }
+                self.add_token(TokenType::LeftBrace);
+            }
+            '}' => match self.interpolation.last_mut() {
+                // Closing brace of an interpolated expression: back into the string
+                Some(open) if open.depth == 0 => {
+                    let open = self.interpolation.pop();
+                    self.string(open)?;
+                }
+                Some(open) => {
+                    open.depth -= 1;
+                    self.add_token(TokenType::RightBrace);
+                }
+                None => self.add_token(TokenType::RightBrace),
+            },
             '[' => self.add_token(TokenType::LeftBracket),
             ']' => self.add_token(TokenType::RightBracket),
             ',' => self.add_token(TokenType::Comma),
-            '.' => self.add_token(TokenType::Dot),
+            ':' => self.add_token(TokenType::Colon),
+            '.' => {
+                if self.match_char('.') {
+                    self.add_token(TokenType::DotDot);
+                } else {
+                    self.add_token(TokenType::Dot);
+                }
+            }
+            '@' => self.add_token(TokenType::At),
             '+' => self.add_token(TokenType::Plus),
             '-' => {
                 if self.match_char('>') {
@@ -51,6 +93,15 @@ impl Lexer {
             }
             '*' => self.add_token(TokenType::Star),
             '%' => self.add_token(TokenType::Percent),
+            '?' => self.add_token(TokenType::Question),
+            '~' => {
+                let token = if self.match_char('/') {
+                    TokenType::TildeSlash
+                } else {
+                    TokenType::Tilde
+                };
+                self.add_token(token);
+            }
             '/' => {
                 if self.match_char('/') {
                     // Comment - skip until end of line
@@ -61,7 +112,7 @@ impl Lexer {
                     self.add_token(TokenType::Slash);
                 }
             }
-            '"' => self.string()?,
+            '"' => self.string(None)?,
             '=' => {
                 let token = if self.match_char('=') {
                     TokenType::EqualEqual
@@ -83,6 +134,8 @@ impl Lexer {
             '<' => {
                 let token = if self.match_char('=') {
                     TokenType::LessEqual
+                } else if self.match_char('<') {
+                    TokenType::LessLess
                 } else {
                     TokenType::Less
                 };
@@ -91,25 +144,30 @@ impl Lexer {
             '>' => {
                 let token = if self.match_char('=') {
                     TokenType::GreaterEqual
+                } else if self.match_char('>') {
+                    TokenType::GreaterGreater
                 } else {
                     TokenType::Greater
                 };
                 self.add_token(token);
             }
             '&' => {
-                if self.match_char('&') {
-                    self.add_token(TokenType::And);
+                let token = if self.match_char('&') {
+                    TokenType::And
                 } else {
-                    return Err(format!("Onverwagte karakter '&' op lyn {}", self.line));
-                }
+                    TokenType::Ampersand
+                };
+                self.add_token(token);
             }
             '|' => {
-                if self.match_char('|') {
-                    self.add_token(TokenType::Or);
+                let token = if self.match_char('|') {
+                    TokenType::Or
                 } else {
-                    return Err(format!("Onverwagte karakter '|' op lyn {}", self.line));
-                }
+                    TokenType::Pipe
+                };
+                self.add_token(token);
             }
+            '^' => self.add_token(TokenType::Caret),
             '\n' => {
                 self.add_token(TokenType::Newline);
                 self.line += 1;
@@ -117,7 +175,9 @@ impl Lexer {
             ' ' | '\r' | '\t' => {}
             _ => {
                 if c.is_ascii_digit() {
-                    self.number();
+                    self.number()?;
+                } else if c == 'r' && self.peek() == '"' {
+                    self.raw_string()?;
                 } else if c.is_alphabetic() || c == '_' {
                     self.identifier();
                 } else {
@@ -129,22 +189,17 @@ impl Lexer {
         Ok(())
     }
 
-    fn number(&mut self) {
-        while self.peek().is_ascii_digit() {
-            self.advance();
-        }
-
-        // Look for decimal part
-        if self.peek() == '.' && self.peek_next().is_ascii_digit() {
-            self.advance(); // consume the '.'
-            while self.peek().is_ascii_digit() {
-                self.advance();
-            }
-        }
-
-        let lexeme: String = self.source[self.start..self.current].iter().collect();
-        let value: f64 = lexeme.parse().unwrap();
-        self.add_token(TokenType::Number(value));
+    fn number(&mut self) -> Result<(), String> {
+        let (literal, end) = scan_number(&self.source, self.start)
+            .map_err(|e| format!("{} op lyn {}", e.message, self.line))?;
+        self.current = end;
+        let token = match literal {
+            NumberLiteral::Float(value) => TokenType::Number(value),
+            NumberLiteral::Integer(value) => TokenType::Integer(value),
+            NumberLiteral::BigInteger(value) => TokenType::BigInteger(value),
+        };
+        self.add_token(token);
+        Ok(())
     }
 
     fn identifier(&mut self) {
@@ -173,6 +228,9 @@ impl Lexer {
             // Module keywords
             "laai" => TokenType::Laai,
             "verskaf" => TokenType::Verskaf,
+            "probeer" => TokenType::Probeer,
+            "vang" => TokenType::Vang,
+            "gooi" => TokenType::Gooi,
             // Wildcard pattern
             "_" => TokenType::Underscore,
             _ => TokenType::Identifier(lexeme.clone()),
@@ -181,21 +239,131 @@ impl Lexer {
         self.add_token(token_type);
     }
 
-    fn string(&mut self) -> Result<(), String> {
+    /// Scan a string literal, or the rest of one after an interpolated
+    /// expression when `resumed` is set. `{expr}` ends the current piece;
+    /// `{{` and `}}` stand for literal braces.
+    fn string(&mut self, resumed: Option<Interpolation>) -> Result<(), String> {
+        let continuation = resumed.is_some();
+        let (start_line, indent) = match resumed {
+            Some(open) => (open.line, open.indent),
+            None => {
+                let line = self.line;
+                if self.peek() == '"' && self.peek_next() == '"' {
+                    self.advance();
+                    self.advance();
+                    (line, Some(self.triple_quote_indent()))
+                } else {
+                    (line, None)
+                }
+            }
+        };
+        let mut raw = String::new();
+
+        loop {
+            if self.is_at_end() {
+                return Err(format!("Onbeëindigde string op lyn {}", start_line));
+            }
+            match self.advance() {
+                '"' if indent.is_none() => break,
+                '"' if self.peek() == '"' && self.peek_next() == '"' => {
+                    self.advance();
+                    self.advance();
+                    // A closing """ on its own line does not end with a newline
+                    if let Some(pos) = raw.rfind('\n') {
+                        if raw[pos + 1..].trim().is_empty() {
+                            raw.truncate(pos);
+                        }
+                    }
+                    break;
+                }
+                '\\' => {
+                    raw.push('\\');
+                    if !self.is_at_end() {
+                        let escaped = self.advance(); // keep the escaped character
+                        raw.push(escaped);
+                        // The braces of \u{...} are not an interpolation
+                        if escaped == 'u' && self.match_char('{') {
+                            raw.push('{');
+                            while !self.is_at_end() && self.peek() != '"' {
+                                let c = self.advance();
+                                raw.push(c);
+                                if c == '}' {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+                '{' if self.match_char('{') => raw.push('{'),
+                '}' if self.match_char('}') => raw.push('}'),
+                '{' => {
+                    let processed = self.process_escapes(&raw).map_err(|e| format!("{} op lyn {}", e, self.line))?;
+                    let token = if continuation {
+                        TokenType::InterpolationMid(processed)
+                    } else {
+                        TokenType::InterpolationStart(processed)
+                    };
+                    self.add_token(token);
+                    self.interpolation.push(Interpolation { depth: 0, line: start_line, indent });
+                    return Ok(());
+                }
+                '\n' => {
+                    self.line += 1;
+                    raw.push('\n');
+                    if let Some(indent) = indent {
+                        self.skip_indent(indent);
+                    }
+                }
+                c => raw.push(c),
+            }
+        }
+
+        // Process escape sequences
+        let processed = self.process_escapes(&raw).map_err(|e| format!("{} op lyn {}", e, self.line))?;
+        let token = if continuation {
+            TokenType::InterpolationEnd(processed)
+        } else {
+            TokenType::Str(processed)
+        };
+        self.add_token(token);
+        Ok(())
+    }
+
+    /// After an opening """: skip the rest of the line when it is empty and
+    /// return the indentation of the first content line, which is stripped
+    /// from every line of the string.
+    fn triple_quote_indent(&mut self) -> usize {
+        self.match_char('\r');
+        if !self.match_char('\n') {
+            return 0;
+        }
+        self.line += 1;
+        let mut indent = 0;
+        while self.peek() == ' ' || self.peek() == '\t' {
+            self.advance();
+            indent += 1;
+        }
+        indent
+    }
+
+    fn skip_indent(&mut self, indent: usize) {
+        for _ in 0..indent {
+            if self.peek() != ' ' && self.peek() != '\t' {
+                break;
+            }
+            self.advance();
+        }
+    }
+
+    /// Raw string r"...": no escapes and no interpolation
+    fn raw_string(&mut self) -> Result<(), String> {
         let start_line = self.line;
+        self.advance(); // consume the opening "
 
         while self.peek() != '"' && !self.is_at_end() {
-            if self.peek() == '\n' {
+            if self.advance() == '\n' {
                 self.line += 1;
             }
-            if self.peek() == '\\' && !self.is_at_end() {
-                self.advance(); // consume the backslash
-                if !self.is_at_end() {
-                    self.advance(); // consume the escaped character
-                }
-            } else {
-                self.advance();
-            }
         }
 
         if self.is_at_end() {
@@ -205,14 +373,8 @@ impl Lexer {
         // Consume the closing "
         self.advance();
 
-        // Extract the string value (without quotes)
-        let value: String = self.source[self.start + 1..self.current - 1]
-            .iter()
-            .collect();
-
-        // Process escape sequences
-        let processed = self.process_escapes(&value)?;
-        self.add_token(TokenType::Str(processed));
+        let value: String = self.source[self.start + 2..self.current - 1].iter().collect();
+        self.add_token(TokenType::Str(value));
         Ok(())
     }
 
@@ -228,6 +390,7 @@ impl Lexer {
                     Some('r') => result.push('\r'),
                     Some('\\') => result.push('\\'),
                     Some('"') => result.push('"'),
+                    Some('u') => result.push(Self::unicode_escape(&mut chars)?),
                     Some(other) => {
                         return Err(format!("Ongeldige ontsnappingskarakter: \\{}", other));
                     }
@@ -243,6 +406,24 @@ impl Lexer {
         Ok(result)
     }
 
+    /// The `{...}` part of a \u{...} escape: one to six hex digits
+    fn unicode_escape(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<char, String> {
+        let invalid = || "Ongeldige Unicode-ontsnapping; verwag \\u{...} met 1 tot 6 heksadesimale syfers".to_string();
+        if chars.next() != Some('{') {
+            return Err(invalid());
+        }
+        let mut hex = String::new();
+        loop {
+            match chars.next() {
+                Some('}') => break,
+                Some(c) if c.is_ascii_hexdigit() && hex.len() < 6 => hex.push(c),
+                _ => return Err(invalid()),
+            }
+        }
+        let code = u32::from_str_radix(&hex, 16).map_err(|_| invalid())?;
+        char::from_u32(code).ok_or_else(|| format!("\\u{{{}}} is nie 'n geldige Unicode-karakter nie", hex))
+    }
+
     fn is_at_end(&self) -> bool {
         self.current >= self.source.len()
     }
@@ -283,3 +464,144 @@ impl Lexer {
         self.tokens.push(Token::new(token_type, lexeme, self.line));
     }
 }
+
+/// The value of a number literal, as scanned by `scan_number`
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberLiteral {
+    Float(f64),
+    Integer(i64),
+    /// An integer literal too large for i64
+    BigInteger(BigInt),
+}
+
+/// A malformed number literal; `end` is where scanning stopped
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberError {
+    pub message: String,
+    pub end: usize,
+}
+
+/// Scan the number literal that starts at `start` and return its value and
+/// the index just past it. Messages carry no position so the lexer and the
+/// language server can each add their own.
+pub fn scan_number(source: &[char], start: usize) -> Result<(NumberLiteral, usize), NumberError> {
+    let mut scanner = NumberScanner { source, start, current: start + 1 };
+    scanner.number().map_err(|message| NumberError { message, end: scanner.current })
+}
+
+struct NumberScanner<'a> {
+    source: &'a [char],
+    start: usize,
+    current: usize,
+}
+
+impl NumberScanner<'_> {
+    fn number(&mut self) -> Result<(NumberLiteral, usize), String> {
+        // Prefixed integer literals: 0x, 0b and 0o
+        if self.source[self.start] == '0' {
+            let radix = match self.peek() {
+                'x' => Some((16, "heksadesimale")),
+                'b' => Some((2, "binêre")),
+                'o' => Some((8, "oktale")),
+                _ => None,
+            };
+            if let Some((radix, name)) = radix {
+                self.advance();
+                let mut digits = String::new();
+                self.digits(radix, &mut digits)?;
+                if digits.is_empty() {
+                    return Err(format!("Onvolledige {} getal '{}'", name, self.lexeme()));
+                }
+                if self.peek().is_ascii_alphanumeric() {
+                    let lexeme = self.lexeme();
+                    let c = self.advance();
+                    return Err(format!("Ongeldige syfer '{}' in {} getal '{}'", c, name, lexeme));
+                }
+                return Ok((integer(&digits, radix), self.current));
+            }
+        }
+
+        let mut text = self.source[self.start].to_string();
+        self.digits(10, &mut text)?;
+        let mut is_float = false;
+
+        // Look for decimal part
+        if self.peek() == '.' && self.peek_next().is_ascii_digit() {
+            self.advance(); // consume the '.'
+            text.push('.');
+            self.digits(10, &mut text)?;
+            is_float = true;
+        }
+
+        // Look for an exponent
+        if self.peek() == 'e' || self.peek() == 'E' {
+            self.advance();
+            text.push('e');
+            if self.peek() == '+' || self.peek() == '-' {
+                text.push(self.advance());
+            }
+            let mut exponent = String::new();
+            self.digits(10, &mut exponent)?;
+            if exponent.is_empty() {
+                return Err(format!("Eksponent sonder syfers in '{}'", self.lexeme()));
+            }
+            text.push_str(&exponent);
+            is_float = true;
+        }
+
+        if is_float {
+            let value: f64 = text.parse().unwrap();
+            if value.is_infinite() {
+                return Err(format!("Getal {} is te groot", self.lexeme()));
+            }
+            return Ok((NumberLiteral::Float(value), self.current));
+        }
+
+        // Without a decimal part or exponent the literal is an integer
+        Ok((integer(&text, 10), self.current))
+    }
+
+    /// Consume digits in the given radix, allowing '_' between digits.
+    /// The digits are appended without separators.
+    fn digits(&mut self, radix: u32, digits: &mut String) -> Result<(), String> {
+        loop {
+            let c = self.peek();
+            if c.is_digit(radix) {
+                digits.push(self.advance());
+            } else if c == '_' && self.source[self.current - 1].is_digit(radix) && self.peek_next().is_digit(radix) {
+                self.advance();
+            } else if c == '_' {
+                self.advance();
+                return Err("'_' in 'n getal moet tussen twee syfers staan".to_string());
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn lexeme(&self) -> String {
+        self.source[self.start..self.current].iter().collect()
+    }
+
+    fn advance(&mut self) -> char {
+        let c = self.source[self.current];
+        self.current += 1;
+        c
+    }
+
+    fn peek(&self) -> char {
+        self.source.get(self.current).copied().unwrap_or('\0')
+    }
+
+    fn peek_next(&self) -> char {
+        self.source.get(self.current + 1).copied().unwrap_or('\0')
+    }
+}
+
+/// An integer literal's value; digits have been checked against the radix
+fn integer(digits: &str, radix: u32) -> NumberLiteral {
+    match i64::from_str_radix(digits, radix) {
+        Ok(value) => NumberLiteral::Integer(value),
+        Err(_) => NumberLiteral::BigInteger(BigInt::from_digits(digits, radix).expect("digits were checked by the lexer")),
+    }
+}
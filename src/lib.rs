@@ -0,0 +1,26 @@
+//! Arkaan as a library: embed the interpreter in a Rust program.
+
+mod ast;
+mod bignum;
+mod bytecode;
+mod compiler;
+mod error;
+pub mod convert;
+pub mod interpreter;
+mod json;
+mod lexer;
+pub mod limits;
+mod numeric;
+mod parser;
+mod random;
+mod token;
+pub mod value;
+pub mod vm;
+
+pub use convert::{FromValue, IntoValue};
+pub use error::{Error, ErrorKind};
+pub use interpreter::{Interpreter, Program, SharedBuffer};
+// The language server scans numbers the same way the interpreter does
+pub use lexer::{scan_number, NumberLiteral};
+pub use limits::Limits;
+pub use value::{Arity, Value};
@@ -0,0 +1,42 @@
+//! Resource limits for running untrusted code.
+
+use std::time::Duration;
+
+/// Every limit error message starts with this. Hosts should match
+/// `Error::Limit` rather than the text, which scripts can fake.
+pub const LIMIT_ERROR_PREFIX: &str = "Limiet oorskry";
+
+/// What a VM may do in one run (`eval`, `call` or a script). `None` means
+/// unlimited; the default has no limits and allows everything.
+#[derive(Debug, Clone)]
+pub struct Limits {
+    /// Bytecode instructions one run may execute
+    pub max_instructions: Option<u64>,
+    /// Depth of nested function calls
+    pub max_call_depth: Option<usize>,
+    /// Elements in a list, tuple, dictionary or set, bytes in a string or
+    /// digits in a number
+    pub max_collection_size: Option<usize>,
+    /// Wall-clock time one run may take
+    pub timeout: Option<Duration>,
+    /// Whether `laai` may load modules
+    pub allow_imports: bool,
+    /// Whether natives may read and write files
+    pub allow_file_io: bool,
+    /// Whether `omgewing` may read environment variables
+    pub allow_environment: bool,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_instructions: None,
+            max_call_depth: None,
+            max_collection_size: None,
+            timeout: None,
+            allow_imports: true,
+            allow_file_io: true,
+            allow_environment: true,
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use arkaan_lang::{scan_number, NumberLiteral};
 use tower_lsp::lsp_types::*;
 
-// We need to duplicate some core logic here since we can't easily share
-// between binaries. In a real project, you'd use a library crate.
+// The lexer and parser are duplicated here so they can keep going after an
+// error and report positions. Number literals come from the library's
+// scanner, because a second copy of those rules drifted from the real one.
 
 #[derive(Debug, Clone, PartialEq)]
 enum TokenType {
@@ -13,16 +15,21 @@ enum TokenType {
     Pas, Geval, Tipe, Of,
     // Module keywords
     Laai, Verskaf,
+    // Error keywords
+    Probeer, Vang, Gooi,
     // Literals and identifiers
     Number(f64), Str(String), Identifier(String),
+    InterpolationStart(String), InterpolationMid(String), InterpolationEnd(String),
     // Operators
-    Plus, Minus, Star, Slash, Percent,
+    Plus, Minus, Star, Slash, TildeSlash, Percent,
     Equal, EqualEqual, Bang, BangEqual,
     Less, LessEqual, Greater, GreaterEqual,
+    Ampersand, Pipe, Caret, Tilde, LessLess, GreaterGreater, Question,
     And, Or, Arrow, FatArrow,
     // Delimiters
     LeftParen, RightParen, LeftBrace, RightBrace,
-    LeftBracket, RightBracket, Comma, Underscore,
+    LeftBracket, RightBracket, Comma, Colon, Underscore,
+    Dot, DotDot, At,
     Newline, Eof,
 }
 
@@ -43,6 +50,9 @@ struct Lexer {
     line: u32,
     col: u32,
     start_col: u32,
+    /// Brace depth inside each open `{expr}` of a string, and whether the
+    /// string is triple-quoted
+    interpolation: Vec<(usize, bool)>,
 }
 
 impl Lexer {
@@ -55,6 +65,7 @@ impl Lexer {
             line: 0,
             col: 0,
             start_col: 0,
+            interpolation: Vec::new(),
         }
     }
 
@@ -86,11 +97,37 @@ impl Lexer {
         match c {
             '(' => self.add_token(TokenType::LeftParen),
             ')' => self.add_token(TokenType::RightParen),
-            '{' => self.add_token(TokenType::LeftBrace),
-            '}' => self.add_token(TokenType::RightBrace),
+            '{' => {
+                if let Some((depth, _)) = self.interpolation.last_mut() {
+                    *depth += 1;
+                }
+                self.add_token(TokenType::LeftBrace);
+            }
+            '}' => match self.interpolation.last_mut() {
+                Some((0, triple)) => {
+                    let triple = *triple;
+                    self.interpolation.pop();
+                    self.string(true, triple)?;
+                }
+                Some((depth, _)) => {
+                    *depth -= 1;
+                    self.add_token(TokenType::RightBrace);
+                }
+                None => self.add_token(TokenType::RightBrace),
+            },
             '[' => self.add_token(TokenType::LeftBracket),
             ']' => self.add_token(TokenType::RightBracket),
             ',' => self.add_token(TokenType::Comma),
+            ':' => self.add_token(TokenType::Colon),
+            '.' => {
+                let token = if self.match_char('.') {
+                    TokenType::DotDot
+                } else {
+                    TokenType::Dot
+                };
+                self.add_token(token);
+            }
+            '@' => self.add_token(TokenType::At),
             '+' => self.add_token(TokenType::Plus),
             '-' => {
                 let token = if self.match_char('>') {
@@ -102,6 +139,15 @@ impl Lexer {
             }
             '*' => self.add_token(TokenType::Star),
             '%' => self.add_token(TokenType::Percent),
+            '?' => self.add_token(TokenType::Question),
+            '~' => {
+                let token = if self.match_char('/') {
+                    TokenType::TildeSlash
+                } else {
+                    TokenType::Tilde
+                };
+                self.add_token(token);
+            }
             '/' => {
                 if self.match_char('/') {
                     while self.peek() != '\n' && !self.is_at_end() {
@@ -132,6 +178,8 @@ impl Lexer {
             '<' => {
                 let token = if self.match_char('=') {
                     TokenType::LessEqual
+                } else if self.match_char('<') {
+                    TokenType::LessLess
                 } else {
                     TokenType::Less
                 };
@@ -140,27 +188,32 @@ impl Lexer {
             '>' => {
                 let token = if self.match_char('=') {
                     TokenType::GreaterEqual
+                } else if self.match_char('>') {
+                    TokenType::GreaterGreater
                 } else {
                     TokenType::Greater
                 };
                 self.add_token(token);
             }
             '&' => {
-                if self.match_char('&') {
-                    self.add_token(TokenType::And);
+                let token = if self.match_char('&') {
+                    TokenType::And
                 } else {
-                    return Err(self.make_diagnostic("Onverwagte karakter '&'. Bedoel jy '&&'?"));
-                }
+                    TokenType::Ampersand
+                };
+                self.add_token(token);
             }
             '|' => {
-                if self.match_char('|') {
-                    self.add_token(TokenType::Or);
+                let token = if self.match_char('|') {
+                    TokenType::Or
                 } else {
-                    return Err(self.make_diagnostic("Onverwagte karakter '|'. Bedoel jy '||'?"));
-                }
+                    TokenType::Pipe
+                };
+                self.add_token(token);
             }
+            '^' => self.add_token(TokenType::Caret),
             '"' => {
-                self.string()?;
+                self.string(false, false)?;
             }
             '\n' => {
                 self.add_token(TokenType::Newline);
@@ -170,7 +223,9 @@ impl Lexer {
             ' ' | '\r' | '\t' => {}
             _ => {
                 if c.is_ascii_digit() {
-                    self.number();
+                    self.number().map_err(|e| self.make_diagnostic(&e))?;
+                } else if c == 'r' && self.peek() == '"' {
+                    self.raw_string().map_err(|e| self.make_diagnostic(&e))?;
                 } else if c.is_alphabetic() || c == '_' {
                     self.identifier();
                 } else {
@@ -182,19 +237,23 @@ impl Lexer {
         Ok(())
     }
 
-    fn number(&mut self) {
-        while self.peek().is_ascii_digit() {
-            self.advance();
-        }
-        if self.peek() == '.' && self.peek_next().is_ascii_digit() {
-            self.advance();
-            while self.peek().is_ascii_digit() {
-                self.advance();
-            }
-        }
-        let lexeme: String = self.source[self.start..self.current].iter().collect();
-        let value: f64 = lexeme.parse().unwrap_or(0.0);
+    /// Scan with the interpreter's own number scanner, so both accept and
+    /// reject the same literals
+    fn number(&mut self) -> std::result::Result<(), String> {
+        let result = scan_number(&self.source, self.start);
+        let end = match &result {
+            Ok((_, end)) => *end,
+            Err(e) => e.end,
+        };
+        self.col += (end - self.current) as u32;
+        self.current = end;
+        let value = match result.map_err(|e| e.message)?.0 {
+            NumberLiteral::Float(value) => value,
+            NumberLiteral::Integer(value) => value as f64,
+            NumberLiteral::BigInteger(value) => value.to_f64(),
+        };
         self.add_token(TokenType::Number(value));
+        Ok(())
     }
 
     fn identifier(&mut self) {
@@ -223,6 +282,9 @@ impl Lexer {
             // Module keywords
             "laai" => TokenType::Laai,
             "verskaf" => TokenType::Verskaf,
+            "probeer" => TokenType::Probeer,
+            "vang" => TokenType::Vang,
+            "gooi" => TokenType::Gooi,
             // Wildcard
             "_" => TokenType::Underscore,
             _ => TokenType::Identifier(lexeme.clone()),
@@ -230,41 +292,101 @@ impl Lexer {
         self.add_token(token_type);
     }
 
-    fn string(&mut self) -> std::result::Result<(), Diagnostic> {
+    /// Scan a string literal, or its continuation after `{expr}`. `triple`
+    /// is set when the string was opened with """.
+    fn string(&mut self, continuation: bool, triple: bool) -> std::result::Result<(), Diagnostic> {
         let start_line = self.line;
         let start_col = self.start_col;
+        let mut value = String::new();
+        let triple = triple || (!continuation && self.peek() == '"' && self.peek_next() == '"');
+        if triple && !continuation {
+            self.advance();
+            self.advance();
+        }
 
-        while self.peek() != '"' && !self.is_at_end() {
-            if self.peek() == '\n' {
-                self.line += 1;
-                self.col = 0;
+        loop {
+            if self.is_at_end() {
+                return Err(Diagnostic {
+                    range: Range {
+                        start: Position { line: start_line, character: start_col },
+                        end: Position { line: self.line, character: self.col },
+                    },
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some("arkaan".to_string()),
+                    message: "Onbeeindigde string - verwag '\"'".to_string(),
+                    ..Default::default()
+                });
             }
-            if self.peek() == '\\' && !self.is_at_end() {
-                self.advance(); // consume backslash
-                if !self.is_at_end() {
-                    self.advance(); // consume escaped char
+            match self.advance() {
+                '"' if !triple => break,
+                '"' if self.peek() == '"' && self.peek_next() == '"' => {
+                    self.advance();
+                    self.advance();
+                    break;
+                }
+                '\\' => {
+                    value.push('\\');
+                    if !self.is_at_end() {
+                        let escaped = self.advance(); // keep escaped char
+                        value.push(escaped);
+                        if escaped == 'u' && self.match_char('{') {
+                            value.push('{');
+                            while !self.is_at_end() && self.peek() != '"' {
+                                let c = self.advance();
+                                value.push(c);
+                                if c == '}' {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+                '{' if self.match_char('{') => value.push('{'),
+                '}' if self.match_char('}') => value.push('}'),
+                '{' => {
+                    let token = if continuation {
+                        TokenType::InterpolationMid(value)
+                    } else {
+                        TokenType::InterpolationStart(value)
+                    };
+                    self.add_token(token);
+                    self.interpolation.push((0, triple));
+                    return Ok(());
+                }
+                c => {
+                    if c == '\n' {
+                        self.line += 1;
+                        self.col = 0;
+                    }
+                    value.push(c);
                 }
-            } else {
-                self.advance();
             }
         }
 
+        let token = if continuation {
+            TokenType::InterpolationEnd(value)
+        } else {
+            TokenType::Str(value)
+        };
+        self.add_token(token);
+        Ok(())
+    }
+
+    /// Raw string r"...": no escapes and no interpolation
+    fn raw_string(&mut self) -> std::result::Result<(), String> {
+        self.advance(); // consume opening "
+        while self.peek() != '"' && !self.is_at_end() {
+            if self.advance() == '\n' {
+                self.line += 1;
+                self.col = 0;
+            }
+        }
         if self.is_at_end() {
-            return Err(Diagnostic {
-                range: Range {
-                    start: Position { line: start_line, character: start_col },
-                    end: Position { line: self.line, character: self.col },
-                },
-                severity: Some(DiagnosticSeverity::ERROR),
-                source: Some("arkaan".to_string()),
-                message: "Onbeeindigde string - verwag '\"'".to_string(),
-                ..Default::default()
-            });
+            return Err("Onbeeindigde string - verwag '\"'".to_string());
         }
-
         self.advance(); // consume closing "
 
-        let value: String = self.source[self.start + 1..self.current - 1].iter().collect();
+        let value: String = self.source[self.start + 2..self.current - 1].iter().collect();
         self.add_token(TokenType::Str(value));
         Ok(())
     }
@@ -323,6 +445,17 @@ impl Lexer {
     }
 }
 
+/// Check if the 'laat' at index `i` starts a destructuring pattern
+fn is_destructuring_laat(tokens: &[Token], i: usize) -> bool {
+    match tokens.get(i + 1).map(|t| &t.token_type) {
+        Some(TokenType::LeftBracket) | Some(TokenType::LeftParen) => true,
+        Some(TokenType::Identifier(_)) => {
+            matches!(tokens.get(i + 2).map(|t| &t.token_type), Some(TokenType::LeftParen))
+        }
+        _ => false,
+    }
+}
+
 // Simple parser for diagnostics
 fn parse_for_diagnostics(tokens: &[Token]) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
@@ -343,6 +476,36 @@ fn parse_for_diagnostics(tokens: &[Token]) -> Vec<Diagnostic> {
         "kaart", "filter", "vou", "vir_elk",
         // List functions
         "lengte", "kop", "stert", "leeg", "voeg_by", "heg_aan", "ketting", "omgekeer",
+        // Dictionary functions
+        "sleutels", "waardes", "kry", "met", "sonder", "bevat",
+        // Set functions
+        "versameling", "vereniging", "snyding", "verskil", "is_deel_van",
+        // Tuple functions
+        "rits",
+        // Number conversion
+        "heel", "desimaal",
+        // Exact numbers
+        "breuk", "presiese_deling",
+        // String functions
+        "verdeel", "voeg_saam", "sny", "trim", "hoofletters", "kleinletters", "begin_met", "eindig_met", "vervang", "indeks_van", "herhaal", "karakters", "na_nommer", "na_string",
+        // Comparison
+        "vergelyk",
+        // List functions
+        "sorteer", "sorteer_met", "sorteer_volgens", "vind", "indeks", "enige", "almal", "groepeer", "uniek", "neem", "los", "zip", "plat", "reeks",
+        // Input / output
+        "skryf", "druk_fout", "lees_lyn", "lees_alles",
+        // Errors
+        "Uitsondering",
+        // Opsie and Resultaat
+        "Sommige", "Niks", "Ok", "Fout", "kaart_opsie", "en_dan", "of_anders", "is_ok", "kop_veilig", "stert_veilig", "kry_veilig", "vind_veilig", "na_nommer_veilig",
+        // File system module
+        "lêer",
+        // JSON
+        "json_lees", "json_skryf",
+        // Process
+        "argumente", "omgewing", "verlaat",
+        // Math
+        "PI", "E", "wortel", "mag", "abs", "vloer", "plafon", "rond", "sin", "cos", "tan", "atan2", "ln", "log", "exp", "min", "maks", "saad", "willekeurig", "willekeurig_heel", "skommel",
     ].iter().cloned().collect();
 
     // First pass: collect all declared constants
@@ -355,6 +518,18 @@ fn parse_for_diagnostics(tokens: &[Token]) -> Vec<Diagnostic> {
                     declared_vars.insert(name.clone());
                 }
             }
+            // Destructuring: laat [a, b] = ... or laat Punt(x, y) = ...
+            if is_destructuring_laat(tokens, j) {
+                let mut k = j + 1;
+                while k < tokens.len() && !matches!(tokens[k].token_type, TokenType::Equal) {
+                    if let TokenType::Identifier(name) = &tokens[k].token_type {
+                        if name.chars().next().map(|c| c.is_lowercase()).unwrap_or(false) {
+                            declared_vars.insert(name.clone());
+                        }
+                    }
+                    k += 1;
+                }
+            }
         }
         // Track 'verskaf laat' declarations
         if matches!(tokens[j].token_type, TokenType::Verskaf) {
@@ -384,6 +559,15 @@ fn parse_for_diagnostics(tokens: &[Token]) -> Vec<Diagnostic> {
                 }
             }
         }
+        // For 'vang (e)' - the caught error's name
+        if matches!(tokens[j].token_type, TokenType::Vang)
+            && j + 2 < tokens.len()
+            && matches!(tokens[j + 1].token_type, TokenType::LeftParen)
+        {
+            if let TokenType::Identifier(name) = &tokens[j + 2].token_type {
+                declared_vars.insert(name.clone());
+            }
+        }
         // For 'geval' pattern matching - collect pattern bindings (identifiers between geval and =>)
         if matches!(tokens[j].token_type, TokenType::Geval) {
             let mut k = j + 1;
@@ -501,8 +685,8 @@ fn parse_for_diagnostics(tokens: &[Token]) -> Vec<Diagnostic> {
                 }
             }
             TokenType::Laat => {
-                // Check for: laat <identifier> = <expr>
-                if i + 1 < tokens.len() {
+                // Check for: laat <identifier> = <expr> (destructuring patterns are left to the compiler)
+                if i + 1 < tokens.len() && !is_destructuring_laat(tokens, i) {
                     if !matches!(tokens[i + 1].token_type, TokenType::Identifier(_)) {
                         diagnostics.push(Diagnostic {
                             range: Range {
@@ -659,6 +843,17 @@ fn parse_for_diagnostics(tokens: &[Token]) -> Vec<Diagnostic> {
                     continue;
                 }
 
+                // Skip field names: punt.x, Punt(x: 1), punt met { x: 3 }
+                let is_field_name = (i > 0 && matches!(tokens[i - 1].token_type, TokenType::Dot))
+                    || (i + 1 < tokens.len() && matches!(tokens[i + 1].token_type, TokenType::Colon))
+                    || (name == "met"
+                        && i + 1 < tokens.len()
+                        && matches!(tokens[i + 1].token_type, TokenType::LeftBrace));
+                if is_field_name {
+                    i += 1;
+                    continue;
+                }
+
                 // Check if this identifier is used as a constant (not being declared)
                 let is_declaration = i > 0 && matches!(
                     tokens[i - 1].token_type,
@@ -823,6 +1018,18 @@ pub fn get_hover_info(text: &str, position: Position) -> Option<Hover> {
                     "**geval** (sleutelwoord)\n\n'n Arm in 'n pas-uitdrukking.\n\n```arkaan\ngeval Sommige(x) => x * 2\n```",
                     "Match arm in pattern matching"
                 )),
+                TokenType::Probeer => Some((
+                    "**probeer** (sleutelwoord)\n\nVoer 'n blok uit en vang enige fout daarin met `vang`.\n\n```arkaan\nprobeer {\n    druk(1 / 0)\n} vang (e) {\n    druk(e.boodskap)\n}\n```\n\nIngeboude foute is `Uitsondering(soort, boodskap, lyn)` waardes.",
+                    "Try block"
+                )),
+                TokenType::Vang => Some((
+                    "**vang** (sleutelwoord)\n\nHanteer 'n fout uit die `probeer` blok. Die naam kry die gegooide waarde.\n\n```arkaan\n} vang (e) {\n    druk(e)\n}\n```",
+                    "Catch handler"
+                )),
+                TokenType::Gooi => Some((
+                    "**gooi** (sleutelwoord)\n\nGooi enige waarde as 'n fout.\n\n```arkaan\ngooi \"iets het skeefgeloop\"\n```",
+                    "Throw a value"
+                )),
                 TokenType::Tipe => Some((
                     "**tipe** (sleutelwoord)\n\nDefinieer 'n algebraïese datatipe.\n\n```arkaan\ntipe Opsie {\n    Niks\n    Sommige(waarde)\n}\n```",
                     "Define algebraic data type"
@@ -877,6 +1084,362 @@ pub fn get_hover_info(text: &str, position: Position) -> Option<Hover> {
                             "**omgekeer** (funksie)\n\nKeer 'n lys om (reverse).\n\n```arkaan\ndruk(omgekeer([1, 2, 3]))  // [3, 2, 1]\n```",
                             "Reverse a list"
                         )),
+                        "sleutels" => Some((
+                            "**sleutels** (funksie)\n\nGee die sleutels van 'n woordeboek as 'n lys.\n\n```arkaan\ndruk(sleutels({\"a\": 1, \"b\": 2}))  // [a, b]\n```",
+                            "Get dictionary keys"
+                        )),
+                        "waardes" => Some((
+                            "**waardes** (funksie)\n\nGee die waardes van 'n woordeboek as 'n lys.\n\n```arkaan\ndruk(waardes({\"a\": 1, \"b\": 2}))  // [1, 2]\n```",
+                            "Get dictionary values"
+                        )),
+                        "kry" => Some((
+                            "**kry** (funksie)\n\nSoek 'n sleutel op, of gee die verstekwaarde.\n\n```arkaan\ndruk(kry({\"a\": 1}, \"b\", 0))  // 0\n```",
+                            "Look up a key with a default"
+                        )),
+                        "met" => Some((
+                            "**met** (funksie)\n\nGee 'n kopie van 'n woordeboek met die sleutel gestel.\n\n```arkaan\ndruk(met({\"a\": 1}, \"b\", 2))  // {a: 1, b: 2}\n```",
+                            "Copy dictionary with a key set"
+                        )),
+                        "sonder" => Some((
+                            "**sonder** (funksie)\n\nGee 'n kopie van 'n woordeboek sonder die sleutel.\n\n```arkaan\ndruk(sonder({\"a\": 1, \"b\": 2}, \"a\"))  // {b: 2}\n```",
+                            "Copy dictionary without a key"
+                        )),
+                        "bevat" => Some((
+                            "**bevat** (funksie)\n\nKyk of 'n woordeboek 'n sleutel, 'n lys 'n element, of 'n string 'n substring bevat.\n\n```arkaan\ndruk(bevat({\"a\": 1}, \"a\"))  // waar\ndruk(bevat([1, 2], 3))      // vals\n```",
+                            "Check for a key, element or substring"
+                        )),
+                        "versameling" => Some((
+                            "**versameling** (funksie)\n\nBou 'n versameling (set) uit die elemente van 'n lys.\n\n```arkaan\ndruk(versameling([1, 2, 1]))  // #{1, 2}\n```",
+                            "Build a set from a list"
+                        )),
+                        "vereniging" => Some((
+                            "**vereniging** (funksie)\n\nGee die elemente wat in enige van twee versamelings is.\n\n```arkaan\ndruk(vereniging(versameling([1]), versameling([2])))  // #{1, 2}\n```",
+                            "Set union"
+                        )),
+                        "snyding" => Some((
+                            "**snyding** (funksie)\n\nGee die elemente wat in albei versamelings is.\n\n```arkaan\ndruk(snyding(versameling([1, 2]), versameling([2, 3])))  // #{2}\n```",
+                            "Set intersection"
+                        )),
+                        "verskil" => Some((
+                            "**verskil** (funksie)\n\nGee die elemente van die eerste versameling wat nie in die tweede is nie.\n\n```arkaan\ndruk(verskil(versameling([1, 2]), versameling([2])))  // #{1}\n```",
+                            "Set difference"
+                        )),
+                        "is_deel_van" => Some((
+                            "**is_deel_van** (funksie)\n\nKyk of elke element van die eerste versameling in die tweede is.\n\n```arkaan\ndruk(is_deel_van(versameling([1]), versameling([1, 2])))  // waar\n```",
+                            "Subset test"
+                        )),
+                        "rits" => Some((
+                            "**rits** (funksie)\n\nKoppel die elemente van twee lyste in 'n lys van tupels.\n\n```arkaan\ndruk(rits([1, 2], [\"a\", \"b\"]))  // [(1, a), (2, b)]\n```",
+                            "Zip two lists into tuples"
+                        )),
+                        "heel" => Some((
+                            "**heel** (funksie)\n\nSkakel 'n nommer of string om na 'n heelgetal (kap die breukdeel af).\n\n```arkaan\ndruk(heel(3.9))   // 3\ndruk(heel(\"42\"))  // 42\n```",
+                            "Convert to integer"
+                        )),
+                        "desimaal" => Some((
+                            "**desimaal** (funksie)\n\nSkakel 'n nommer of string om na 'n desimale getal.\n\n```arkaan\ndruk(desimaal(3) / 2)  // 1.5\n```",
+                            "Convert to float"
+                        )),
+                        "breuk" => Some((
+                            "**breuk** (funksie)\n\nMaak 'n presiese breuk in laagste terme.\n\n```arkaan\ndruk(breuk(2, 6))            // 1/3\ndruk(breuk(1, 3) * 3)        // 1\n```",
+                            "Exact fraction"
+                        )),
+                        "presiese_deling" => Some((
+                            "**presiese_deling** (funksie)\n\nSkakel presiese deling aan of af: met `waar` gee `/` op heelgetalle 'n breuk in plaas van 'n desimaal.\n\n```arkaan\npresiese_deling(waar)\ndruk(1 / 3)  // 1/3\n```",
+                            "Toggle exact integer division"
+                        )),
+                        "verdeel" => Some((
+                            "**verdeel** (funksie)\n\nVerdeel 'n string by elke skeier. 'n Leë skeier gee die karakters.\n\n```arkaan\ndruk(verdeel(\"a,b,c\", \",\"))  // [a, b, c]\n```",
+                            "Split a string"
+                        )),
+                        "voeg_saam" => Some((
+                            "**voeg_saam** (funksie)\n\nVoeg die elemente van 'n lys saam in een string.\n\n```arkaan\ndruk(voeg_saam([\"a\", \"b\"], \"-\"))  // a-b\n```",
+                            "Join list into string"
+                        )),
+                        "sny" => Some((
+                            "**sny** (funksie)\n\nKarakters (of lys-elemente) van `begin` tot (nie ingesluit nie) `einde`. Negatiewe posisies tel van agter af.\n\n```arkaan\ndruk(sny(\"Arkaan\", 0, 3))  // Ark\n```",
+                            "Slice a string"
+                        )),
+                        "trim" => Some((
+                            "**trim** (funksie)\n\nVerwyder spasies aan albei kante.\n\n```arkaan\ndruk(trim(\"  hallo  \"))  // hallo\n```",
+                            "Trim whitespace"
+                        )),
+                        "hoofletters" => Some((
+                            "**hoofletters** (funksie)\n\nSkakel om na hoofletters.\n\n```arkaan\ndruk(hoofletters(\"hallo\"))  // HALLO\n```",
+                            "Upper case"
+                        )),
+                        "kleinletters" => Some((
+                            "**kleinletters** (funksie)\n\nSkakel om na kleinletters.\n\n```arkaan\ndruk(kleinletters(\"HALLO\"))  // hallo\n```",
+                            "Lower case"
+                        )),
+                        "begin_met" => Some((
+                            "**begin_met** (funksie)\n\nBegin die string met die voorvoegsel?\n\n```arkaan\ndruk(begin_met(\"Arkaan\", \"Ark\"))  // waar\n```",
+                            "Starts with prefix"
+                        )),
+                        "eindig_met" => Some((
+                            "**eindig_met** (funksie)\n\nEindig die string met die agtervoegsel?\n\n```arkaan\ndruk(eindig_met(\"Arkaan\", \"kaan\"))  // waar\n```",
+                            "Ends with suffix"
+                        )),
+                        "vervang" => Some((
+                            "**vervang** (funksie)\n\nVervang elke voorkoms van `oud` met `nuut`.\n\n```arkaan\ndruk(vervang(\"a-b-c\", \"-\", \"+\"))  // a+b+c\n```",
+                            "Replace all occurrences"
+                        )),
+                        "indeks_van" => Some((
+                            "**indeks_van** (funksie)\n\nKarakterposisie van die eerste voorkoms, of -1.\n\n```arkaan\ndruk(indeks_van(\"Arkaan\", \"kaan\"))  // 2\n```",
+                            "Index of substring"
+                        )),
+                        "herhaal" => Some((
+                            "**herhaal** (funksie)\n\nHerhaal 'n string `n` keer.\n\n```arkaan\ndruk(herhaal(\"ab\", 3))  // ababab\n```",
+                            "Repeat string"
+                        )),
+                        "karakters" => Some((
+                            "**karakters** (funksie)\n\nLys van die karakters in 'n string.\n\n```arkaan\ndruk(karakters(\"abc\"))  // [a, b, c]\n```",
+                            "Characters of string"
+                        )),
+                        "na_nommer" => Some((
+                            "**na_nommer** (funksie)\n\nLees 'n string as heelgetal of desimaal.\n\n```arkaan\ndruk(na_nommer(\"42\") + 1)  // 43\n```",
+                            "Parse number"
+                        )),
+                        "na_string" => Some((
+                            "**na_string** (funksie)\n\nDie waarde as string, soos `druk` dit sou wys.\n\n```arkaan\ndruk(na_string(12) + \"3\")  // 123\n```",
+                            "Convert to string"
+                        )),
+                        "vergelyk" => Some((
+                            "**vergelyk** (funksie)\n\nGee -1, 0 of 1 as `a` kleiner as, gelyk aan of groter as `b` is. Werk op nommers, stringe, lyste, tupels en ADTs.\n\n```arkaan\ndruk(vergelyk(\"a\", \"b\"))  // -1\n```",
+                            "Three-way comparison"
+                        )),
+                        "sorteer" => Some((
+                            "**sorteer** (funksie)\n\nSorteer 'n lys stabiel van klein na groot.\n\n```arkaan\ndruk(sorteer([3, 1, 2]))  // [1, 2, 3]\n```",
+                            "Stable sort"
+                        )),
+                        "sorteer_met" => Some((
+                            "**sorteer_met** (funksie)\n\nSorteer stabiel met 'n vergelykingsfunksie wat -1, 0 of 1 gee.\n\n```arkaan\ndruk(sorteer_met([1, 3, 2], fn(a, b) vergelyk(b, a)))  // [3, 2, 1]\n```",
+                            "Sort with comparator"
+                        )),
+                        "sorteer_volgens" => Some((
+                            "**sorteer_volgens** (funksie)\n\nSorteer stabiel volgens die sleutel wat die funksie vir elke element gee.\n\n```arkaan\ndruk(sorteer_volgens([\"ccc\", \"a\"], lengte))  // [a, ccc]\n```",
+                            "Sort by key"
+                        )),
+                        "vind" => Some((
+                            "**vind** (funksie)\n\nDie eerste element waarvoor die funksie waar is, of nil.\n\n```arkaan\ndruk(vind([1, 4, 9], fn(x) x > 3))  // 4\n```",
+                            "Find first match"
+                        )),
+                        "indeks" => Some((
+                            "**indeks** (funksie)\n\nPosisie van die eerste element waarvoor die funksie waar is, of -1.\n\n```arkaan\ndruk(indeks([\"a\", \"b\"], fn(x) x == \"b\"))  // 1\n```",
+                            "Index of first match"
+                        )),
+                        "enige" => Some((
+                            "**enige** (funksie)\n\nIs die funksie waar vir enige element?\n\n```arkaan\ndruk(enige([1, 2], fn(x) x > 1))  // waar\n```",
+                            "Any element matches"
+                        )),
+                        "almal" => Some((
+                            "**almal** (funksie)\n\nIs die funksie waar vir elke element?\n\n```arkaan\ndruk(almal([1, 2], fn(x) x > 1))  // vals\n```",
+                            "All elements match"
+                        )),
+                        "groepeer" => Some((
+                            "**groepeer** (funksie)\n\nGroepeer elemente in 'n woordeboek volgens die sleutel wat die funksie gee.\n\n```arkaan\ndruk(groepeer([1, 2, 3], fn(x) x % 2))  // {1: [1, 3], 0: [2]}\n```",
+                            "Group by key"
+                        )),
+                        "uniek" => Some((
+                            "**uniek** (funksie)\n\nDie lys sonder herhaalde elemente.\n\n```arkaan\ndruk(uniek([1, 2, 1]))  // [1, 2]\n```",
+                            "Remove duplicates"
+                        )),
+                        "neem" => Some((
+                            "**neem** (funksie)\n\nDie eerste `n` elemente.\n\n```arkaan\ndruk(neem([1, 2, 3], 2))  // [1, 2]\n```",
+                            "Take first n"
+                        )),
+                        "los" => Some((
+                            "**los** (funksie)\n\nAlles na die eerste `n` elemente.\n\n```arkaan\ndruk(los([1, 2, 3], 2))  // [3]\n```",
+                            "Drop first n"
+                        )),
+                        "zip" => Some((
+                            "**zip** (funksie)\n\nDieselfde as `rits`: koppel elemente van twee lyste in tupels.\n\n```arkaan\ndruk(zip([1, 2], [\"a\", \"b\"]))  // [(1, a), (2, b)]\n```",
+                            "Zip two lists"
+                        )),
+                        "plat" => Some((
+                            "**plat** (funksie)\n\nMaak een vlak geneste lyste plat.\n\n```arkaan\ndruk(plat([[1, 2], [3]]))  // [1, 2, 3]\n```",
+                            "Flatten one level"
+                        )),
+                        "reeks" => Some((
+                            "**reeks** (funksie)\n\nGetalle van `begin` tot (nie ingesluit nie) `einde` met `stap`.\n\n```arkaan\ndruk(reeks(0, 10, 3))  // [0, 3, 6, 9]\n```",
+                            "Range of numbers"
+                        )),
+                        "skryf" => Some((
+                            "**skryf** (funksie)\n\nDruk 'n waarde sonder 'n nuwe lyn.\n\n```arkaan\nskryf(\"Naam: \")\n```",
+                            "Print without newline"
+                        )),
+                        "druk_fout" => Some((
+                            "**druk_fout** (funksie)\n\nDruk 'n waarde na die foutstroom (stderr).\n\n```arkaan\ndruk_fout(\"Iets het skeefgeloop\")\n```",
+                            "Print to stderr"
+                        )),
+                        "lees_lyn" => Some((
+                            "**lees_lyn** (funksie)\n\nLees een reël invoer sonder die nuwe lyn. Gee nil aan die einde van die invoer.\n\n```arkaan\nlaat naam = lees_lyn()\n```",
+                            "Read one line of input"
+                        )),
+                        "lees_alles" => Some((
+                            "**lees_alles** (funksie)\n\nLees die res van die invoer as een string.\n\n```arkaan\nlaat teks = lees_alles()\n```",
+                            "Read all remaining input"
+                        )),
+                        "Sommige" => Some((
+                            "**Sommige**(waarde) -> Opsie\n\n'n Opsie met 'n waarde.",
+                            "Some value"
+                        )),
+                        "Niks" => Some((
+                            "**Niks** -> Opsie\n\n'n Opsie sonder waarde.",
+                            "No value"
+                        )),
+                        "Ok" => Some((
+                            "**Ok**(waarde) -> Resultaat\n\n'n Suksesvolle Resultaat.",
+                            "Successful result"
+                        )),
+                        "Fout" => Some((
+                            "**Fout**(fout) -> Resultaat\n\n'n Mislukte Resultaat.",
+                            "Failed result"
+                        )),
+                        "kaart_opsie" => Some((
+                            "**kaart_opsie**(o, f) -> Opsie | Resultaat\n\nPas `f` toe op die waarde in 'n `Sommige` of `Ok`.\n\n```arkaan\nkaart_opsie(Sommige(2), fn(x) x * 3)  // Sommige(6)\n```",
+                            "Map the value inside an option or result"
+                        )),
+                        "en_dan" => Some((
+                            "**en_dan**(o, f) -> Opsie | Resultaat\n\nRoep `f` met die waarde in 'n `Sommige` of `Ok`; `f` gee self 'n Opsie of Resultaat.\n\n```arkaan\nen_dan(Ok(4), fn(x) Ok(x + 1))  // Ok(5)\n```",
+                            "Chain an operation that may fail"
+                        )),
+                        "of_anders" => Some((
+                            "**of_anders**(o, standaard) -> waarde\n\nDie waarde in 'n `Sommige` of `Ok`, anders `standaard`.\n\n```arkaan\nof_anders(Niks, 0)  // 0\n```",
+                            "Unwrap with a default"
+                        )),
+                        "is_ok" => Some((
+                            "**is_ok**(o) -> boolean\n\n`waar` vir 'n `Sommige` of `Ok`.",
+                            "Check for success"
+                        )),
+                        "kop_veilig" => Some((
+                            "**kop_veilig**(lys) -> Opsie\n\nDie eerste element, of `Niks` vir 'n leë lys.",
+                            "First element, if any"
+                        )),
+                        "stert_veilig" => Some((
+                            "**stert_veilig**(lys) -> Opsie\n\nAlles behalwe die eerste element, of `Niks` vir 'n leë lys.",
+                            "Rest of the list, if any"
+                        )),
+                        "kry_veilig" => Some((
+                            "**kry_veilig**(versameling, sleutel) -> Opsie\n\n'n Woordeboek waarde of lys element, of `Niks` as dit nie bestaan nie.",
+                            "Look up a key or index, if present"
+                        )),
+                        "vind_veilig" => Some((
+                            "**vind_veilig**(lys, f) -> Opsie\n\nDie eerste element waarvoor `f` waar is, of `Niks`.",
+                            "Find an element, if any"
+                        )),
+                        "na_nommer_veilig" => Some((
+                            "**na_nommer_veilig**(s) -> Resultaat\n\nLees 'n nommer; `Fout` as die string nie 'n nommer is nie.\n\n```arkaan\nna_nommer_veilig(\"12\")  // Ok(12)\n```",
+                            "Parse a number as a result"
+                        )),
+                        "lêer" => Some((
+                            "**lêer** (module)\n\nLees en skryf lêers. Relatiewe paaie is relatief tot die huidige lêer; foute is `Fout` waardes.\n\n- `lees(pad)`, `skryf(pad, teks)`, `voeg_by_lêer(pad, teks)`\n- `bestaan(pad)`, `lys_gids(pad)`, `skep_gids(pad)`, `verwyder(pad)`, `verwyder_alles(pad)`\n- `pad_saam(deel, ...)`\n\n```arkaan\nlaat inhoud = lêer.lees(\"data.txt\")\n```",
+                            "File system module"
+                        )),
+                        "json_lees" => Some((
+                            "**json_lees**(s) -> waarde\n\nLees 'n JSON dokument. Objekte word woordeboeke, skikkings lyste en `null` nil.\n\n```arkaan\nlaat data = json_lees(teks)\n```",
+                            "Parse JSON"
+                        )),
+                        "json_skryf" => Some((
+                            "**json_skryf**(waarde, mooi?) -> string\n\nSkryf 'n waarde as JSON; met `mooi` as `waar` word dit ingekeep.\n\n```arkaan\ndruk(json_skryf({\"a\": [1, 2]}))  // {\"a\":[1,2]}\n```",
+                            "Write a value as JSON"
+                        )),
+                        "argumente" => Some((
+                            "**argumente**() -> lys\n\nDie program se opdragreël argumente as strings.\n\n```arkaan\n// arkaan skrip.ark -- a b\ndruk(argumente())  // [a, b]\n```",
+                            "Command-line arguments"
+                        )),
+                        "omgewing" => Some((
+                            "**omgewing**(naam) -> Opsie\n\n`Sommige(waarde)` van 'n omgewingsveranderlike, of `Niks` as dit nie gestel is nie.\n\n```arkaan\nlaat tuis = of_anders(omgewing(\"HOME\"), \".\")\n```",
+                            "Read an environment variable"
+                        )),
+                        "verlaat" => Some((
+                            "**verlaat**(kode?) -> nooit\n\nStop die program met 'n uittreekode (verstek 0). Kan nie gevang word nie.\n\n```arkaan\nverlaat(1)\n```",
+                            "Exit with a status code"
+                        )),
+                        "PI" => Some((
+                            "**PI** (konstante)\n\nDie verhouding van 'n sirkel se omtrek tot sy deursnee, 3.14159...",
+                            "Pi"
+                        )),
+                        "E" => Some((
+                            "**E** (konstante)\n\nDie basis van die natuurlike logaritme, 2.71828...",
+                            "Euler's number"
+                        )),
+                        "wortel" => Some((
+                            "**wortel**(x) -> nommer\n\nVierkantswortel van 'n nie-negatiewe nommer.\n\n```arkaan\nwortel(16)  // 4\n```",
+                            "Square root"
+                        )),
+                        "mag" => Some((
+                            "**mag**(basis, eksponent) -> nommer\n\n`basis` tot die mag `eksponent`; presies vir heelgetal eksponente.\n\n```arkaan\nmag(2, 10)  // 1024\n```",
+                            "Raise to a power"
+                        )),
+                        "abs" => Some((
+                            "**abs**(x) -> nommer\n\nAbsolute waarde.\n\n```arkaan\nabs(-5)  // 5\n```",
+                            "Absolute value"
+                        )),
+                        "vloer" => Some((
+                            "**vloer**(x) -> heelgetal\n\nRond af na die grootste heelgetal nie groter as `x` nie.\n\n```arkaan\nvloer(-2.5)  // -3\n```",
+                            "Round down"
+                        )),
+                        "plafon" => Some((
+                            "**plafon**(x) -> heelgetal\n\nRond op na die kleinste heelgetal nie kleiner as `x` nie.\n\n```arkaan\nplafon(2.1)  // 3\n```",
+                            "Round up"
+                        )),
+                        "rond" => Some((
+                            "**rond**(x) -> heelgetal\n\nRond na die naaste heelgetal; helftes weg van nul.\n\n```arkaan\nrond(2.5)  // 3\n```",
+                            "Round to nearest"
+                        )),
+                        "sin" => Some((
+                            "**sin**(x) -> nommer\n\nSinus van 'n hoek in radiale.",
+                            "Sine"
+                        )),
+                        "cos" => Some((
+                            "**cos**(x) -> nommer\n\nKosinus van 'n hoek in radiale.",
+                            "Cosine"
+                        )),
+                        "tan" => Some((
+                            "**tan**(x) -> nommer\n\nTangens van 'n hoek in radiale.",
+                            "Tangent"
+                        )),
+                        "atan2" => Some((
+                            "**atan2**(y, x) -> nommer\n\nDie hoek in radiale van die punt (x, y).",
+                            "Two-argument arctangent"
+                        )),
+                        "ln" => Some((
+                            "**ln**(x) -> nommer\n\nNatuurlike logaritme.",
+                            "Natural logarithm"
+                        )),
+                        "log" => Some((
+                            "**log**(x, basis?) -> nommer\n\nLogaritme, met basis 10 tensy anders gegee.\n\n```arkaan\nlog(8, 2)  // 3\n```",
+                            "Logarithm"
+                        )),
+                        "exp" => Some((
+                            "**exp**(x) -> nommer\n\n`E` tot die mag `x`.",
+                            "Exponential"
+                        )),
+                        "min" => Some((
+                            "**min**(lys) -> waarde\n\nDie kleinste waarde in 'n lys, of van verskeie argumente.\n\n```arkaan\nmin([3, 1, 2])  // 1\n```",
+                            "Minimum"
+                        )),
+                        "maks" => Some((
+                            "**maks**(lys) -> waarde\n\nDie grootste waarde in 'n lys, of van verskeie argumente.\n\n```arkaan\nmaks(4, 9)  // 9\n```",
+                            "Maximum"
+                        )),
+                        "saad" => Some((
+                            "**saad**(n)\n\nSaai die ewekansige getalgenerator sodat die getalle elke keer dieselfde is.",
+                            "Seed the random generator"
+                        )),
+                        "willekeurig" => Some((
+                            "**willekeurig**() -> nommer\n\n'n Ewekansige nommer van 0 tot (maar nie) 1 nie.",
+                            "Random float"
+                        )),
+                        "willekeurig_heel" => Some((
+                            "**willekeurig_heel**(min, maks) -> heelgetal\n\n'n Ewekansige heelgetal van `min` tot en met `maks`.",
+                            "Random integer"
+                        )),
+                        "skommel" => Some((
+                            "**skommel**(lys) -> lys\n\n'n Kopie van die lys in ewekansige volgorde.",
+                            "Shuffle a list"
+                        )),
                         _ => None,
                     }
                 }
@@ -929,6 +1492,22 @@ pub fn get_completions(text: &str, position: Position) -> Vec<CompletionItem> {
             insert_text_format: Some(InsertTextFormat::SNIPPET),
             ..Default::default()
         },
+        CompletionItem {
+            label: "probeer".to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            detail: Some("Probeer-vang blok".to_string()),
+            insert_text: Some("probeer {\n\t${1}\n} vang (${2:e}) {\n\t${0}\n}".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "gooi".to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            detail: Some("Gooi 'n fout".to_string()),
+            insert_text: Some("gooi ${0}".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
         CompletionItem {
             label: "gee...as".to_string(),
             kind: Some(CompletionItemKind::KEYWORD),
@@ -1102,78 +1681,811 @@ pub fn get_completions(text: &str, position: Position) -> Vec<CompletionItem> {
             insert_text_format: Some(InsertTextFormat::SNIPPET),
             ..Default::default()
         },
-        // Booleans
         CompletionItem {
-            label: "waar".to_string(),
-            kind: Some(CompletionItemKind::CONSTANT),
-            detail: Some("Boolean waar (true)".to_string()),
+            label: "sleutels".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Sleutels van woordeboek".to_string()),
+            insert_text: Some("sleutels(${0:woordeboek})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
             ..Default::default()
         },
         CompletionItem {
-            label: "vals".to_string(),
-            kind: Some(CompletionItemKind::CONSTANT),
-            detail: Some("Boolean vals (false)".to_string()),
+            label: "waardes".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Waardes van woordeboek".to_string()),
+            insert_text: Some("waardes(${0:woordeboek})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
             ..Default::default()
         },
-    ];
-
-    // Extract constant names from the document
-    let mut lexer = Lexer::new(text);
-    let (tokens, _) = lexer.scan_tokens();
-
-    let mut seen_vars = std::collections::HashSet::new();
-    let mut i = 0;
-    while i < tokens.len() {
-        // Constant declarations (laat)
-        if matches!(tokens[i].token_type, TokenType::Laat) {
-            if i + 1 < tokens.len() {
-                if let TokenType::Identifier(name) = &tokens[i + 1].token_type {
-                    if !seen_vars.contains(name) {
-                        seen_vars.insert(name.clone());
-                        completions.push(CompletionItem {
-                            label: name.clone(),
-                            kind: Some(CompletionItemKind::CONSTANT),
-                            detail: Some("Konstante".to_string()),
-                            ..Default::default()
-                        });
-                    }
-                }
-            }
-        }
-        i += 1;
-    }
-
-    // Extract type constructors from document
-    let mut seen_constructors = std::collections::HashSet::new();
-    i = 0;
-    while i < tokens.len() {
-        if matches!(tokens[i].token_type, TokenType::Tipe) {
-            // Skip type name and opening brace
-            if i + 2 < tokens.len() && matches!(tokens[i + 2].token_type, TokenType::LeftBrace) {
-                let mut k = i + 3;
-                while k < tokens.len() && !matches!(tokens[k].token_type, TokenType::RightBrace) {
-                    if let TokenType::Identifier(name) = &tokens[k].token_type {
-                        // Constructor names typically start uppercase
-                        if name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false)
-                            && !seen_constructors.contains(name)
-                        {
-                            seen_constructors.insert(name.clone());
-                            completions.push(CompletionItem {
-                                label: name.clone(),
-                                kind: Some(CompletionItemKind::CONSTRUCTOR),
-                                detail: Some("Tipe konstruktor".to_string()),
-                                insert_text: Some(format!("{}(${{0}})", name)),
-                                insert_text_format: Some(InsertTextFormat::SNIPPET),
-                                ..Default::default()
-                            });
-                        }
-                    }
-                    k += 1;
-                }
-            }
-        }
-        i += 1;
-    }
-
-    completions
+        CompletionItem {
+            label: "kry".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Soek sleutel met verstek".to_string()),
+            insert_text: Some("kry(${1:woordeboek}, ${2:sleutel}, ${0:verstek})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "met".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Woordeboek met sleutel".to_string()),
+            insert_text: Some("met(${1:woordeboek}, ${2:sleutel}, ${0:waarde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "sonder".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Woordeboek sonder sleutel".to_string()),
+            insert_text: Some("sonder(${1:woordeboek}, ${0:sleutel})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "bevat".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Kyk of waarde bevat word".to_string()),
+            insert_text: Some("bevat(${1:versameling}, ${0:waarde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "versameling".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Versameling uit lys".to_string()),
+            insert_text: Some("versameling(${0:lys})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "vereniging".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Vereniging van versamelings".to_string()),
+            insert_text: Some("vereniging(${1:a}, ${0:b})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "snyding".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Snyding van versamelings".to_string()),
+            insert_text: Some("snyding(${1:a}, ${0:b})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "verskil".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Verskil van versamelings".to_string()),
+            insert_text: Some("verskil(${1:a}, ${0:b})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "is_deel_van".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Kyk vir deelversameling".to_string()),
+            insert_text: Some("is_deel_van(${1:a}, ${0:b})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "rits".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Koppel twee lyste".to_string()),
+            insert_text: Some("rits(${1:lys1}, ${0:lys2})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "heel".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Skakel om na heelgetal".to_string()),
+            insert_text: Some("heel(${0:waarde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "desimaal".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Skakel om na desimaal".to_string()),
+            insert_text: Some("desimaal(${0:waarde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "breuk".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Presiese breuk".to_string()),
+            insert_text: Some("breuk(${1:teller}, ${0:noemer})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "presiese_deling".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Presiese deling aan/af".to_string()),
+            insert_text: Some("presiese_deling(${0:waar})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "verdeel".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Verdeel string".to_string()),
+            insert_text: Some("verdeel(${1:s}, ${0:skeier})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "voeg_saam".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Voeg lys saam".to_string()),
+            insert_text: Some("voeg_saam(${1:lys}, ${0:skeier})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "sny".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Sny string".to_string()),
+            insert_text: Some("sny(${1:s}, ${2:begin}, ${0:einde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "trim".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Verwyder spasies".to_string()),
+            insert_text: Some("trim(${0:s})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "hoofletters".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Hoofletters".to_string()),
+            insert_text: Some("hoofletters(${0:s})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "kleinletters".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Kleinletters".to_string()),
+            insert_text: Some("kleinletters(${0:s})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "begin_met".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Begin met".to_string()),
+            insert_text: Some("begin_met(${1:s}, ${0:voorvoegsel})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "eindig_met".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Eindig met".to_string()),
+            insert_text: Some("eindig_met(${1:s}, ${0:agtervoegsel})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "vervang".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Vervang".to_string()),
+            insert_text: Some("vervang(${1:s}, ${2:oud}, ${0:nuut})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "indeks_van".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Indeks van".to_string()),
+            insert_text: Some("indeks_van(${1:s}, ${0:deel})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "herhaal".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Herhaal".to_string()),
+            insert_text: Some("herhaal(${1:s}, ${0:n})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "karakters".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Karakters".to_string()),
+            insert_text: Some("karakters(${0:s})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "na_nommer".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Na nommer".to_string()),
+            insert_text: Some("na_nommer(${0:s})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "na_string".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Na string".to_string()),
+            insert_text: Some("na_string(${0:waarde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "vergelyk".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Vergelyk twee waardes".to_string()),
+            insert_text: Some("vergelyk(${1:a}, ${0:b})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "sorteer".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Sorteer lys".to_string()),
+            insert_text: Some("sorteer(${0:lys})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "sorteer_met".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Sorteer met funksie".to_string()),
+            insert_text: Some("sorteer_met(${1:lys}, fn(${2:a}, ${3:b}) ${0})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "sorteer_volgens".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Sorteer volgens sleutel".to_string()),
+            insert_text: Some("sorteer_volgens(${1:lys}, fn(${2:x}) ${0})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "vind".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Vind element".to_string()),
+            insert_text: Some("vind(${1:lys}, fn(${2:x}) ${0})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "indeks".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Indeks van element".to_string()),
+            insert_text: Some("indeks(${1:lys}, fn(${2:x}) ${0})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "enige".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Enige".to_string()),
+            insert_text: Some("enige(${1:lys}, fn(${2:x}) ${0})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "almal".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Almal".to_string()),
+            insert_text: Some("almal(${1:lys}, fn(${2:x}) ${0})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "groepeer".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Groepeer".to_string()),
+            insert_text: Some("groepeer(${1:lys}, fn(${2:x}) ${0})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "uniek".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Uniek".to_string()),
+            insert_text: Some("uniek(${0:lys})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "neem".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Neem".to_string()),
+            insert_text: Some("neem(${1:lys}, ${0:n})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "los".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Los".to_string()),
+            insert_text: Some("los(${1:lys}, ${0:n})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "zip".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Zip".to_string()),
+            insert_text: Some("zip(${1:lys1}, ${0:lys2})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "plat".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Plat".to_string()),
+            insert_text: Some("plat(${0:lys})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "reeks".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Reeks".to_string()),
+            insert_text: Some("reeks(${1:begin}, ${2:einde}, ${0:stap})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "skryf".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Druk sonder nuwe lyn".to_string()),
+            insert_text: Some("skryf(${1:waarde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "druk_fout".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Druk na stderr".to_string()),
+            insert_text: Some("druk_fout(${1:waarde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "lees_lyn".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Lees een reël".to_string()),
+            insert_text: Some("lees_lyn()".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "lees_alles".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Lees alle invoer".to_string()),
+            insert_text: Some("lees_alles()".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "Sommige".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("Opsie met waarde".to_string()),
+            insert_text: Some("Sommige(${1:waarde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "Niks".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("leë Opsie".to_string()),
+            insert_text: Some("Niks".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "Ok".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("suksesvolle Resultaat".to_string()),
+            insert_text: Some("Ok(${1:waarde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "Fout".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("mislukte Resultaat".to_string()),
+            insert_text: Some("Fout(${1:fout})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "kaart_opsie".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("pas f toe op die waarde".to_string()),
+            insert_text: Some("kaart_opsie(${1:o}, ${2:f})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "en_dan".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("ketting van opsies".to_string()),
+            insert_text: Some("en_dan(${1:o}, ${2:f})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "of_anders".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("waarde of standaard".to_string()),
+            insert_text: Some("of_anders(${1:o}, ${2:standaard})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "is_ok".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("is dit Sommige of Ok?".to_string()),
+            insert_text: Some("is_ok(${1:o})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "kop_veilig".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("eerste element as Opsie".to_string()),
+            insert_text: Some("kop_veilig(${1:lys})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "stert_veilig".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("stert as Opsie".to_string()),
+            insert_text: Some("stert_veilig(${1:lys})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "kry_veilig".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("opsoek as Opsie".to_string()),
+            insert_text: Some("kry_veilig(${1:versameling}, ${2:sleutel})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "vind_veilig".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("vind as Opsie".to_string()),
+            insert_text: Some("vind_veilig(${1:lys}, ${2:f})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "na_nommer_veilig".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("string na nommer as Resultaat".to_string()),
+            insert_text: Some("na_nommer_veilig(${1:s})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "lêer".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("lêerstelsel module".to_string()),
+            insert_text: Some("lêer.${1:lees}(${2})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "json_lees".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("JSON na waarde".to_string()),
+            insert_text: Some("json_lees(${1:teks})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "json_skryf".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("waarde na JSON".to_string()),
+            insert_text: Some("json_skryf(${1:waarde})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "argumente".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("opdragreël argumente".to_string()),
+            insert_text: Some("argumente()".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "omgewing".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("omgewingsveranderlike".to_string()),
+            insert_text: Some("omgewing(${1:naam})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "verlaat".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("stop die program".to_string()),
+            insert_text: Some("verlaat(${1:0})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "PI".to_string(),
+            kind: Some(CompletionItemKind::CONSTANT),
+            detail: Some("π".to_string()),
+            insert_text: Some("PI".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "E".to_string(),
+            kind: Some(CompletionItemKind::CONSTANT),
+            detail: Some("e".to_string()),
+            insert_text: Some("E".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "wortel".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("vierkantswortel".to_string()),
+            insert_text: Some("wortel(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "mag".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("mag".to_string()),
+            insert_text: Some("mag(${1:basis}, ${2:eksponent})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "abs".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("absolute waarde".to_string()),
+            insert_text: Some("abs(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "vloer".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("rond af".to_string()),
+            insert_text: Some("vloer(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "plafon".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("rond op".to_string()),
+            insert_text: Some("plafon(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "rond".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("rond na naaste".to_string()),
+            insert_text: Some("rond(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "sin".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("sinus".to_string()),
+            insert_text: Some("sin(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "cos".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("kosinus".to_string()),
+            insert_text: Some("cos(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "tan".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("tangens".to_string()),
+            insert_text: Some("tan(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "atan2".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("boogtangens".to_string()),
+            insert_text: Some("atan2(${1:y}, ${2:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "ln".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("natuurlike logaritme".to_string()),
+            insert_text: Some("ln(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "log".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("logaritme".to_string()),
+            insert_text: Some("log(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "exp".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("eksponensiaal".to_string()),
+            insert_text: Some("exp(${1:x})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "min".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("kleinste waarde".to_string()),
+            insert_text: Some("min(${1:lys})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "maks".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("grootste waarde".to_string()),
+            insert_text: Some("maks(${1:lys})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "saad".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("saai generator".to_string()),
+            insert_text: Some("saad(${1:42})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "willekeurig".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("ewekansige nommer".to_string()),
+            insert_text: Some("willekeurig()".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "willekeurig_heel".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("ewekansige heelgetal".to_string()),
+            insert_text: Some("willekeurig_heel(${1:1}, ${2:6})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "skommel".to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some("skommel lys".to_string()),
+            insert_text: Some("skommel(${1:lys})".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
+        // Booleans
+        CompletionItem {
+            label: "waar".to_string(),
+            kind: Some(CompletionItemKind::CONSTANT),
+            detail: Some("Boolean waar (true)".to_string()),
+            ..Default::default()
+        },
+        CompletionItem {
+            label: "vals".to_string(),
+            kind: Some(CompletionItemKind::CONSTANT),
+            detail: Some("Boolean vals (false)".to_string()),
+            ..Default::default()
+        },
+    ];
+
+    // Extract constant names from the document
+    let mut lexer = Lexer::new(text);
+    let (tokens, _) = lexer.scan_tokens();
+
+    let mut seen_vars = std::collections::HashSet::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        // Constant declarations (laat)
+        if matches!(tokens[i].token_type, TokenType::Laat) {
+            if i + 1 < tokens.len() {
+                if let TokenType::Identifier(name) = &tokens[i + 1].token_type {
+                    if !seen_vars.contains(name) {
+                        seen_vars.insert(name.clone());
+                        completions.push(CompletionItem {
+                            label: name.clone(),
+                            kind: Some(CompletionItemKind::CONSTANT),
+                            detail: Some("Konstante".to_string()),
+                            ..Default::default()
+                        });
+                    }
+                }
+            }
+        }
+        i += 1;
+    }
+
+    // Extract type constructors from document
+    let mut seen_constructors = std::collections::HashSet::new();
+    i = 0;
+    while i < tokens.len() {
+        if matches!(tokens[i].token_type, TokenType::Tipe) {
+            // Skip type name and opening brace
+            if i + 2 < tokens.len() && matches!(tokens[i + 2].token_type, TokenType::LeftBrace) {
+                let mut k = i + 3;
+                while k < tokens.len() && !matches!(tokens[k].token_type, TokenType::RightBrace) {
+                    if let TokenType::Identifier(name) = &tokens[k].token_type {
+                        // Constructor names typically start uppercase
+                        if name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false)
+                            && !seen_constructors.contains(name)
+                        {
+                            seen_constructors.insert(name.clone());
+                            completions.push(CompletionItem {
+                                label: name.clone(),
+                                kind: Some(CompletionItemKind::CONSTRUCTOR),
+                                detail: Some("Tipe konstruktor".to_string()),
+                                insert_text: Some(format!("{}(${{0}})", name)),
+                                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                                ..Default::default()
+                            });
+                        }
+                    }
+                    k += 1;
+                }
+            }
+        }
+        i += 1;
+    }
+
+    completions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixed_literals_beyond_i64_are_accepted() {
+        assert!(analyze_document("druk(0xFFFFFFFFFFFFFFFF)").is_empty());
+        assert!(analyze_document("druk(0b1_0000000000000000000000000000000000000000000000000000000000000000)").is_empty());
+        assert!(analyze_document("druk(0o7777777777777777777777777)").is_empty());
+    }
+
+    #[test]
+    fn float_literals_too_large_are_reported() {
+        let diagnostics = analyze_document("druk(1e400)");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Getal 1e400 is te groot");
+        assert_eq!(diagnostics[0].range.start.character, 5);
+        assert_eq!(diagnostics[0].range.end.character, 10);
+    }
 }
@@ -1,75 +1,62 @@
-mod ast;
-mod bytecode;
-mod compiler;
-mod lexer;
-mod parser;
-mod token;
-mod value;
-mod vm;
-
 use std::env;
 use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
 
-use compiler::Compiler;
-use lexer::Lexer;
-use parser::Parser;
-use vm::VM;
+use arkaan_lang::{Error, Interpreter};
+
+// Exit codes (from BSD sysexits); a script can choose its own with verlaat()
+const EXIT_USAGE: i32 = 64; // Bad command line
+const EXIT_SYNTAX: i32 = 65; // The script doesn't parse or compile
+const EXIT_NO_INPUT: i32 = 66; // The script can't be read
+const EXIT_RUNTIME: i32 = 70; // Uncaught runtime error
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    match args.len() {
-        1 => repl(),
-        2 => run_file(&args[1]),
-        _ => {
-            eprintln!("Gebruik: arkaan [lêer.ark]");
-            std::process::exit(64);
+    match args.get(1).map(String::as_str) {
+        None => repl(),
+        Some("--") => usage(),
+        Some(path) => {
+            // Everything after the script belongs to it; a '--' before it is optional
+            let mut script_args = &args[2..];
+            if script_args.first().map(String::as_str) == Some("--") {
+                script_args = &script_args[1..];
+            }
+            run_file(path, script_args.to_vec());
         }
     }
 }
 
-fn run_file(path: &str) {
+fn usage() {
+    eprintln!("Gebruik: arkaan [lêer.ark [--] [argumente...]]");
+    std::process::exit(EXIT_USAGE);
+}
+
+fn run_file(path: &str, arguments: Vec<String>) {
     let source = match fs::read_to_string(path) {
         Ok(content) => content,
         Err(e) => {
             eprintln!("Kon nie lêer lees nie: {}", e);
-            std::process::exit(66);
+            std::process::exit(EXIT_NO_INPUT);
         }
     };
 
-    if let Err(e) = run_with_path(&source, path) {
-        eprintln!("Fout: {}", e);
-        std::process::exit(70);
-    }
-}
-
-fn run_with_path(source: &str, path: &str) -> Result<(), String> {
-    // Lexing
-    let mut lexer = Lexer::new(source);
-    let tokens = lexer.scan_tokens()?;
-
-    // Parsing
-    let mut parser = Parser::new(tokens);
-    let statements = parser.parse()?;
-
-    // Compiling
-    let mut compiler = Compiler::new();
-    let (chunk, functions) = compiler.compile(statements)?;
+    let mut interpreter = Interpreter::new();
+    interpreter.set_script_path(path);
+    interpreter.set_arguments(arguments);
 
-    // Executing
-    let mut vm = VM::new(chunk, functions);
-
-    // Set the current file path for relative imports
-    let file_path = PathBuf::from(path);
-    if let Ok(canonical) = file_path.canonicalize() {
-        vm.set_current_file(canonical);
-    } else {
-        vm.set_current_file(file_path);
+    match interpreter.eval(&source) {
+        Ok(_) => {}
+        Err(Error::Exit(code)) => std::process::exit(code),
+        Err(e @ Error::Syntax(_)) => {
+            eprintln!("Fout: {}", e);
+            std::process::exit(EXIT_SYNTAX);
+        }
+        Err(e) => {
+            eprintln!("Fout: {}", e);
+            std::process::exit(EXIT_RUNTIME);
+        }
     }
-
-    vm.run()
 }
 
 fn repl() {
@@ -97,26 +84,10 @@ fn repl() {
             continue;
         }
 
-        if let Err(e) = run(line) {
-            eprintln!("Fout: {}", e);
+        match Interpreter::new().eval(line) {
+            Ok(_) => {}
+            Err(Error::Exit(code)) => std::process::exit(code),
+            Err(e) => eprintln!("Fout: {}", e),
         }
     }
 }
-
-fn run(source: &str) -> Result<(), String> {
-    // Lexing
-    let mut lexer = Lexer::new(source);
-    let tokens = lexer.scan_tokens()?;
-
-    // Parsing
-    let mut parser = Parser::new(tokens);
-    let statements = parser.parse()?;
-
-    // Compiling
-    let mut compiler = Compiler::new();
-    let (chunk, functions) = compiler.compile(statements)?;
-
-    // Executing
-    let mut vm = VM::new(chunk, functions);
-    vm.run()
-}
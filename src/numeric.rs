@@ -0,0 +1,397 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use crate::bignum::{BigInt, Rational};
+use crate::bytecode::OpCode;
+use crate::error::{ErrorKind, Failure};
+use crate::value::Value;
+
+// The numeric tower: Integer (i64) grows into BigInt on overflow, and exact
+// division of integers gives a Rational. Number (f64) is the inexact type;
+// mixing it with any exact value gives a float.
+//
+// Exact values are always normalised: a BigInt never fits in an i64, and a
+// Rational never has a denominator of 1.
+
+/// Wrap a big integer, demoting it to Integer when it fits
+pub fn from_bigint(n: BigInt) -> Value {
+    match n.to_i64() {
+        Some(i) => Value::Integer(i),
+        None => Value::BigInt(Rc::new(n)),
+    }
+}
+
+/// Wrap a fraction, demoting it to an integer when the denominator is 1
+pub fn from_rational(r: Rational) -> Value {
+    if r.is_integer() {
+        from_bigint(r.numer().clone())
+    } else {
+        Value::Rational(Rc::new(r))
+    }
+}
+
+/// Exact value of a finite float (as an integer when it is whole)
+pub fn exact_from_f64(n: f64) -> Option<Value> {
+    Rational::from_f64(n).map(from_rational)
+}
+
+pub fn is_exact(value: &Value) -> bool {
+    matches!(value, Value::Integer(_) | Value::BigInt(_) | Value::Rational(_))
+}
+
+fn to_bigint(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Integer(i) => Some(BigInt::from_i64(*i)),
+        Value::BigInt(n) => Some(n.as_ref().clone()),
+        _ => None,
+    }
+}
+
+fn to_rational(value: &Value) -> Option<Rational> {
+    match value {
+        Value::Rational(r) => Some(r.as_ref().clone()),
+        _ => to_bigint(value).map(Rational::from_integer),
+    }
+}
+
+fn is_zero(value: &Value) -> bool {
+    match value {
+        Value::Integer(i) => *i == 0,
+        Value::Number(n) => *n == 0.0,
+        // Normalised big integers and fractions are never zero
+        _ => false,
+    }
+}
+
+/// Arithmetic on two numbers. '/' gives a float, or an exact fraction when
+/// `exact_division` is on; '~/' rounds down, and '%' takes the sign of the divisor.
+pub fn arithmetic(op: &OpCode, a: &Value, b: &Value, exact_division: bool) -> Result<Value, Failure> {
+    let symbol = match op {
+        OpCode::Add => "+",
+        OpCode::Subtract => "-",
+        OpCode::Multiply => "*",
+        OpCode::Divide => "/",
+        OpCode::IntDivide => "~/",
+        OpCode::Modulo => "%",
+        _ => return Err((ErrorKind::Runtime, "Onbekende rekenkundige operasie.".to_string())),
+    };
+
+    if a.as_f64().is_none() || b.as_f64().is_none() {
+        return Err((ErrorKind::Type, format!("Operande moet nommers wees vir '{}'.", symbol)));
+    }
+    if is_zero(b) && matches!(op, OpCode::Divide | OpCode::IntDivide | OpCode::Modulo) {
+        let message = if symbol == "%" { "Modulo deur nul." } else { "Deling deur nul." };
+        return Err((ErrorKind::DivisionByZero, message.to_string()));
+    }
+
+    // Fast path for two small integers
+    if let (Value::Integer(x), Value::Integer(y)) = (a, b) {
+        let (x, y) = (*x, *y);
+        let result = match op {
+            OpCode::Add => x.checked_add(y),
+            OpCode::Subtract => x.checked_sub(y),
+            OpCode::Multiply => x.checked_mul(y),
+            OpCode::Divide if !exact_division => return Ok(Value::Number(x as f64 / y as f64)),
+            OpCode::Divide => None,
+            OpCode::IntDivide => x.checked_div(y).map(|q| {
+                if x % y != 0 && ((x < 0) != (y < 0)) { q - 1 } else { q }
+            }),
+            _ => {
+                // i64::MIN % -1 overflows in Rust, but the remainder is 0
+                let r = x.checked_rem(y).unwrap_or(0);
+                Some(if r != 0 && ((r < 0) != (y < 0)) { r + y } else { r })
+            }
+        };
+        if let Some(result) = result {
+            return Ok(Value::Integer(result));
+        }
+        // Overflow or exact division: continue with big numbers
+    }
+
+    if is_exact(a) && is_exact(b) {
+        let has_fraction = matches!(a, Value::Rational(_)) || matches!(b, Value::Rational(_));
+        let divide = matches!(op, OpCode::Divide);
+        if divide && !has_fraction && !exact_division {
+            return Ok(Value::Number(a.as_f64().unwrap() / b.as_f64().unwrap()));
+        }
+
+        if has_fraction || divide {
+            let (x, y) = (to_rational(a).unwrap(), to_rational(b).unwrap());
+            return Ok(match op {
+                OpCode::Add => from_rational(x.add(&y)),
+                OpCode::Subtract => from_rational(x.sub(&y)),
+                OpCode::Multiply => from_rational(x.mul(&y)),
+                OpCode::Divide => from_rational(x.div(&y)),
+                OpCode::IntDivide => from_bigint(x.div(&y).floor()),
+                _ => {
+                    let quotient = Rational::from_integer(x.div(&y).floor());
+                    from_rational(x.sub(&y.mul(&quotient)))
+                }
+            });
+        }
+
+        let (x, y) = (to_bigint(a).unwrap(), to_bigint(b).unwrap());
+        return Ok(match op {
+            OpCode::Add => from_bigint(x.add(&y)),
+            OpCode::Subtract => from_bigint(x.sub(&y)),
+            OpCode::Multiply => from_bigint(x.mul(&y)),
+            OpCode::IntDivide => from_bigint(x.div_mod_floor(&y).0),
+            _ => from_bigint(x.div_mod_floor(&y).1),
+        });
+    }
+
+    let (x, y) = (a.as_f64().unwrap(), b.as_f64().unwrap());
+    let result = match op {
+        OpCode::Add => x + y,
+        OpCode::Subtract => x - y,
+        OpCode::Multiply => x * y,
+        OpCode::Divide => x / y,
+        OpCode::IntDivide => (x / y).floor(),
+        _ => {
+            let r = x % y;
+            if r != 0.0 && ((r < 0.0) != (y < 0.0)) { r + y } else { r }
+        }
+    };
+    Ok(Value::Number(result))
+}
+
+pub fn negate(value: &Value) -> Result<Value, Failure> {
+    match value {
+        Value::Number(n) => Ok(Value::Number(-n)),
+        Value::Integer(i) => Ok(match i.checked_neg() {
+            Some(n) => Value::Integer(n),
+            None => from_bigint(BigInt::from_i64(*i).neg()),
+        }),
+        Value::BigInt(n) => Ok(from_bigint(n.neg())),
+        Value::Rational(r) => Ok(from_rational(Rational::new(r.numer().neg(), r.denom().clone()))),
+        _ => Err((ErrorKind::Type, "Operand moet 'n nommer wees vir negasie.".to_string())),
+    }
+}
+
+/// Integer operand of a bitwise operator. Whole floats are accepted;
+/// fractions and non-numbers are not.
+fn bit_operand(value: &Value, symbol: &str) -> Result<BigInt, Failure> {
+    match value {
+        Value::Integer(i) => Ok(BigInt::from_i64(*i)),
+        Value::BigInt(n) => Ok(n.as_ref().clone()),
+        Value::Number(n) => BigInt::from_f64(*n).ok_or_else(|| {
+            (ErrorKind::Type, format!("Operande moet heelgetalle wees vir '{}', nie {} nie.", symbol, n))
+        }),
+        Value::Rational(r) => Err((
+            ErrorKind::Type,
+            format!("Operande moet heelgetalle wees vir '{}', nie {} nie.", symbol, r),
+        )),
+        _ => Err((ErrorKind::Type, format!("Operande moet heelgetalle wees vir '{}'.", symbol))),
+    }
+}
+
+/// Bitwise operators on integers. '&', '|' and '^' need 64-bit operands;
+/// shifts also work on big integers, and '<<' grows into one if needed.
+pub fn bitwise(op: &OpCode, a: &Value, b: &Value) -> Result<Value, Failure> {
+    let symbol = match op {
+        OpCode::BitAnd => "&",
+        OpCode::BitOr => "|",
+        OpCode::BitXor => "^",
+        OpCode::ShiftLeft => "<<",
+        OpCode::ShiftRight => ">>",
+        _ => return Err((ErrorKind::Runtime, "Onbekende bitsgewyse operasie.".to_string())),
+    };
+
+    let x = bit_operand(a, symbol)?;
+    let y = bit_operand(b, symbol)?;
+
+    if matches!(op, OpCode::ShiftLeft | OpCode::ShiftRight) {
+        let amount = match y.to_i64() {
+            Some(n) if n < 0 => {
+                return Err((ErrorKind::Value, format!("Kan nie met 'n negatiewe getal skuif nie ('{}').", symbol)))
+            }
+            Some(n) if n <= MAX_SHIFT => n as usize,
+            _ => return Err((ErrorKind::Value, format!("Skuif van {} bisse is te groot ('{}').", y, symbol))),
+        };
+        let factor = BigInt::from_i64(1).shl(amount);
+        return Ok(if matches!(op, OpCode::ShiftLeft) {
+            from_bigint(x.mul(&factor))
+        } else {
+            // Arithmetic shift: rounds toward negative infinity
+            from_bigint(x.div_mod_floor(&factor).0)
+        });
+    }
+
+    let (x, y) = match (x.to_i64(), y.to_i64()) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return Err((ErrorKind::Runtime, format!("'{}' werk slegs op 64-bis heelgetalle.", symbol))),
+    };
+    Ok(Value::Integer(match op {
+        OpCode::BitAnd => x & y,
+        OpCode::BitOr => x | y,
+        _ => x ^ y,
+    }))
+}
+
+/// Upper bound on the bits needed to store a number exactly; 64 for
+/// machine integers and floats
+pub fn size_bits(value: &Value) -> u64 {
+    match value {
+        Value::BigInt(n) => n.bits(),
+        Value::Rational(r) => r.numer().bits() + r.denom().bits(),
+        _ => 64,
+    }
+}
+
+/// Upper bound on the bits of an arithmetic or shift result, so callers can
+/// refuse oversized numbers before computing them
+pub fn result_bits(op: &OpCode, a: &Value, b: &Value) -> u64 {
+    let (x, y) = (size_bits(a), size_bits(b));
+    match op {
+        OpCode::ShiftLeft => x + b.as_f64().map_or(0, |n| n.clamp(0.0, MAX_SHIFT as f64) as u64),
+        // Machine numbers give at most a 128-bit product, which is cheap
+        _ if x <= 64 && y <= 64 => 64,
+        OpCode::ShiftRight => x,
+        // Fractions are added and divided by cross-multiplying
+        _ => x + y + 1,
+    }
+}
+
+/// Largest shift amount accepted by '<<' and '>>'
+const MAX_SHIFT: i64 = 1 << 20;
+
+/// Bitwise not: ~x == -x - 1, which also holds for big integers
+pub fn bit_not(value: &Value) -> Result<Value, Failure> {
+    let x = bit_operand(value, "~")?;
+    Ok(from_bigint(x.neg().sub(&BigInt::from_i64(1))))
+}
+
+/// Order two numbers. None when either value is not a number; Some(None)
+/// when a NaN is involved.
+pub fn compare(a: &Value, b: &Value) -> Option<Option<Ordering>> {
+    match (a, b) {
+        (Value::Integer(x), Value::Integer(y)) => Some(Some(x.cmp(y))),
+        _ if is_exact(a) && is_exact(b) => Some(Some(to_rational(a)?.cmp(&to_rational(b)?))),
+        _ => Some(a.as_f64()?.partial_cmp(&b.as_f64()?)),
+    }
+}
+
+/// Numeric equality across representations. A float equals an exact value
+/// only when it represents exactly the same number.
+pub fn numbers_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x == y,
+        (Value::Integer(x), Value::Integer(y)) => x == y,
+        (Value::BigInt(x), Value::BigInt(y)) => x == y,
+        (Value::Rational(x), Value::Rational(y)) => x == y,
+        (Value::Number(n), exact) | (exact, Value::Number(n)) if is_exact(exact) => {
+            match exact_from_f64(*n) {
+                Some(converted) => numbers_equal(&converted, exact),
+                None => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// How `round` picks an integer
+#[derive(Clone, Copy)]
+pub enum Rounding {
+    Floor,
+    Ceil,
+    /// Halves round away from zero
+    Nearest,
+}
+
+/// Round a number to an integer. Exact values stay exact; a float that
+/// is not finite has no integer to round to.
+pub fn round(value: &Value, rounding: Rounding, name: &str) -> Result<Value, Failure> {
+    match value {
+        Value::Integer(_) | Value::BigInt(_) => Ok(value.clone()),
+        Value::Rational(r) => {
+            let half = Rational::new(BigInt::from_i64(1), BigInt::from_i64(2));
+            let rounded = match rounding {
+                Rounding::Floor => r.floor(),
+                Rounding::Ceil => Rational::new(r.numer().neg(), r.denom().clone()).floor().neg(),
+                Rounding::Nearest if r.numer().is_negative() => {
+                    Rational::new(r.numer().neg(), r.denom().clone()).add(&half).floor().neg()
+                }
+                Rounding::Nearest => r.add(&half).floor(),
+            };
+            Ok(from_bigint(rounded))
+        }
+        Value::Number(n) => {
+            let rounded = match rounding {
+                Rounding::Floor => n.floor(),
+                Rounding::Ceil => n.ceil(),
+                Rounding::Nearest => n.round(),
+            };
+            BigInt::from_f64(rounded)
+                .map(from_bigint)
+                .ok_or_else(|| (ErrorKind::Value, format!("{}() kan nie {} afrond nie.", name, n)))
+        }
+        _ => Err((ErrorKind::Type, format!("{}() verwag 'n nommer.", name))),
+    }
+}
+
+pub fn abs(value: &Value) -> Result<Value, Failure> {
+    match value {
+        Value::Number(n) => Ok(Value::Number(n.abs())),
+        _ if compare(value, &Value::Integer(0)) == Some(Some(Ordering::Less)) => negate(value),
+        _ if is_exact(value) => Ok(value.clone()),
+        _ => Err((ErrorKind::Type, "abs() verwag 'n nommer.".to_string())),
+    }
+}
+
+/// `base` to the power `exponent`. Exact bases with integer exponents give
+/// exact results (a negative exponent divides like '/'); anything else is a float.
+pub fn power(base: &Value, exponent: &Value, exact_division: bool) -> Result<Value, Failure> {
+    if is_exact(base) {
+        if let Value::Integer(e) = exponent {
+            if power_bits(base, exponent) > MAX_POWER_BITS {
+                return Err((ErrorKind::Value, format!("mag() eksponent {} is te groot vir basis {}.", e, base)));
+            }
+            let mut result = Value::Integer(1);
+            let mut square = base.clone();
+            let mut remaining = e.unsigned_abs();
+            while remaining > 0 {
+                if remaining & 1 == 1 {
+                    result = arithmetic(&OpCode::Multiply, &result, &square, exact_division)?;
+                }
+                remaining >>= 1;
+                if remaining > 0 {
+                    square = arithmetic(&OpCode::Multiply, &square, &square, exact_division)?;
+                }
+            }
+            if *e < 0 {
+                result = arithmetic(&OpCode::Divide, &Value::Integer(1), &result, exact_division)?;
+            }
+            return Ok(result);
+        }
+    }
+    match (base.as_f64(), exponent.as_f64()) {
+        (Some(b), Some(e)) => {
+            let result = b.powf(e);
+            if result.is_nan() {
+                Err((ErrorKind::Value, format!("mag() kan nie {} tot die mag {} verhef nie.", base, exponent)))
+            } else {
+                Ok(Value::Number(result))
+            }
+        }
+        _ => Err((ErrorKind::Type, "mag() verwag twee nommers.".to_string())),
+    }
+}
+
+/// Largest result, in bits, that an exact 'mag' may build (as for '<<')
+const MAX_POWER_BITS: u64 = 1 << 20;
+
+/// Upper bound on the bits of an exact power. 0, 1 and -1 stay small
+/// whatever the exponent; other results grow with it.
+pub fn power_bits(base: &Value, exponent: &Value) -> u64 {
+    let e = match exponent {
+        Value::Integer(e) => e.unsigned_abs(),
+        _ => return 64,
+    };
+    let bits = match base {
+        Value::Integer(b) if b.unsigned_abs() <= 1 => return 64,
+        Value::Integer(b) => 64 - b.unsigned_abs().leading_zeros() as u64,
+        Value::BigInt(_) | Value::Rational(_) => size_bits(base),
+        _ => return 64,
+    };
+    bits.saturating_mul(e)
+}
@@ -1,5 +1,9 @@
+use std::cmp::Ordering;
+
 use crate::ast::{Expr, LambdaBody, Literal, MatchArm, Pattern, Stmt, TypeConstructor};
+use crate::numeric;
 use crate::token::{Token, TokenType};
+use crate::value::Value;
 
 pub struct Parser {
     tokens: Vec<Token>,
@@ -75,10 +79,14 @@ impl Parser {
         // verskaf laat name = ...
         if self.check(&TokenType::Laat) {
             self.advance();
-            let Stmt::VarDecl { name, initializer } = self.var_declaration()? else {
-                unreachable!()
-            };
-            Ok(Stmt::ExportVarDecl { name, initializer })
+            match self.var_declaration()? {
+                Stmt::VarDecl { name, initializer } => Ok(Stmt::ExportVarDecl { name, initializer }),
+                Stmt::PatternDecl { line, .. } => Err(format!(
+                    "Kan nie 'n patroon verskaf nie; verskaf elke naam apart. (lyn {})",
+                    line
+                )),
+                _ => unreachable!(),
+            }
         } else {
             Err(format!(
                 "Verwag 'laat' na 'verskaf'. (lyn {})",
@@ -122,7 +130,15 @@ impl Parser {
 
             if !self.check(&TokenType::RightParen) {
                 loop {
-                    fields.push(self.consume_identifier("Verwag veld naam.")?);
+                    let line = self.peek().line;
+                    let field = self.consume_identifier("Verwag veld naam.")?;
+                    if fields.contains(&field) {
+                        return Err(format!(
+                            "Veld '{}' is meer as een keer in konstruktor '{}' verklaar. (lyn {})",
+                            field, name, line
+                        ));
+                    }
+                    fields.push(field);
                     if !self.check(&TokenType::Comma) {
                         break;
                     }
@@ -140,6 +156,27 @@ impl Parser {
     }
 
     fn var_declaration(&mut self) -> Result<Stmt, String> {
+        let line = self.peek().line;
+
+        // Destructuring declaration: laat [a, b] = paar, laat Punt(x, y) = p
+        if !self.check_plain_name(&TokenType::Equal) {
+            if !self.check(&TokenType::Identifier(String::new()))
+                && !self.check(&TokenType::LeftBracket)
+                && !self.check(&TokenType::LeftParen)
+            {
+                return Err(format!("Verwag konstante naam. (lyn {})", line));
+            }
+            let pattern = self.parse_pattern()?;
+            self.consume(&TokenType::Equal, "Verwag '=' na patroon.")?;
+            let initializer = self.expression()?;
+            self.consume_newline_or_eof()?;
+            return Ok(Stmt::PatternDecl {
+                pattern,
+                initializer,
+                line,
+            });
+        }
+
         let name = self.consume_identifier("Verwag konstante naam.")?;
         self.consume(&TokenType::Equal, "Verwag '=' na konstante naam.")?;
         let initializer = self.expression()?;
@@ -160,6 +197,10 @@ impl Parser {
         } else if self.check(&TokenType::Terwyl) {
             self.advance();
             self.while_statement()
+        } else if self.check(&TokenType::Probeer) {
+            self.try_statement()
+        } else if self.check(&TokenType::Gooi) {
+            self.throw_statement()
         } else if self.check(&TokenType::LeftBrace) {
             self.advance();
             Ok(Stmt::Block(self.block()?))
@@ -254,6 +295,36 @@ impl Parser {
         })
     }
 
+    fn try_statement(&mut self) -> Result<Stmt, String> {
+        let line = self.advance().line; // consume 'probeer'
+        self.skip_newlines();
+        self.consume(&TokenType::LeftBrace, "Verwag '{' na 'probeer'.")?;
+        let body = self.block()?;
+        self.skip_newlines();
+
+        self.consume(&TokenType::Vang, "Verwag 'vang' na 'probeer' blok.")?;
+        self.consume(&TokenType::LeftParen, "Verwag '(' na 'vang'.")?;
+        let name = self.consume_identifier("Verwag 'n naam vir die fout na 'vang ('.")?;
+        self.consume(&TokenType::RightParen, "Verwag ')' na die naam van die fout.")?;
+        self.skip_newlines();
+        self.consume(&TokenType::LeftBrace, "Verwag '{' na 'vang (...)'.")?;
+        let handler = self.block()?;
+
+        Ok(Stmt::Try {
+            body,
+            name,
+            handler,
+            line,
+        })
+    }
+
+    fn throw_statement(&mut self) -> Result<Stmt, String> {
+        let line = self.advance().line; // consume 'gooi'
+        let value = self.expression()?;
+        self.consume_newline_or_eof()?;
+        Ok(Stmt::Throw { value, line })
+    }
+
     fn block(&mut self) -> Result<Vec<Stmt>, String> {
         let mut statements = Vec::new();
 
@@ -285,15 +356,16 @@ impl Parser {
             let right = self.or()?;
 
             expr = match right {
-                Expr::Call { callee, mut arguments } => {
+                Expr::Call { callee, mut arguments, line } => {
                     arguments.insert(0, expr);
-                    Expr::Call { callee, arguments }
+                    Expr::Call { callee, arguments, line }
                 }
                 Expr::Variable(_) | Expr::MemberAccess { .. } |
                 Expr::Lambda { .. } | Expr::Grouping(_) => {
                     Expr::Call {
                         callee: Box::new(right),
                         arguments: vec![expr],
+                        line: self.tokens[self.current - 1].line,
                     }
                 }
                 _ => {
@@ -356,13 +428,77 @@ impl Parser {
     }
 
     fn comparison(&mut self) -> Result<Expr, String> {
-        let mut expr = self.term()?;
+        let mut expr = self.bit_or()?;
 
         while self.check(&TokenType::Less)
             || self.check(&TokenType::LessEqual)
             || self.check(&TokenType::Greater)
             || self.check(&TokenType::GreaterEqual)
         {
+            let operator = self.advance().clone();
+            let right = self.bit_or()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                operator,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn bit_or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.bit_xor()?;
+
+        while self.check(&TokenType::Pipe) {
+            let operator = self.advance().clone();
+            let right = self.bit_xor()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                operator,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn bit_xor(&mut self) -> Result<Expr, String> {
+        let mut expr = self.bit_and()?;
+
+        while self.check(&TokenType::Caret) {
+            let operator = self.advance().clone();
+            let right = self.bit_and()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                operator,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn bit_and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.shift()?;
+
+        while self.check(&TokenType::Ampersand) {
+            let operator = self.advance().clone();
+            let right = self.shift()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                operator,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn shift(&mut self) -> Result<Expr, String> {
+        let mut expr = self.term()?;
+
+        while self.check(&TokenType::LessLess) || self.check(&TokenType::GreaterGreater) {
             let operator = self.advance().clone();
             let right = self.term()?;
             expr = Expr::Binary {
@@ -396,6 +532,7 @@ impl Parser {
 
         while self.check(&TokenType::Star)
             || self.check(&TokenType::Slash)
+            || self.check(&TokenType::TildeSlash)
             || self.check(&TokenType::Percent)
         {
             let operator = self.advance().clone();
@@ -411,7 +548,7 @@ impl Parser {
     }
 
     fn unary(&mut self) -> Result<Expr, String> {
-        if self.check(&TokenType::Bang) || self.check(&TokenType::Minus) {
+        if self.check(&TokenType::Bang) || self.check(&TokenType::Minus) || self.check(&TokenType::Tilde) {
             let operator = self.advance().clone();
             let right = self.unary()?;
             return Ok(Expr::Unary {
@@ -428,15 +565,22 @@ impl Parser {
 
         loop {
             if self.check(&TokenType::LeftParen) {
-                self.advance();
-                expr = self.finish_call(expr)?;
+                let line = self.advance().line;
+                expr = self.finish_call(expr, line)?;
             } else if self.check(&TokenType::LeftBracket) {
-                self.advance();
+                let line = self.advance().line;
                 let index = self.expression()?;
                 self.consume(&TokenType::RightBracket, "Verwag ']' na indeks.")?;
                 expr = Expr::Index {
                     object: Box::new(expr),
                     index: Box::new(index),
+                    line,
+                };
+            } else if self.check(&TokenType::Question) {
+                let line = self.advance().line;
+                expr = Expr::Propagate {
+                    value: Box::new(expr),
+                    line,
                 };
             } else if self.check(&TokenType::Dot) {
                 self.advance();
@@ -445,6 +589,16 @@ impl Parser {
                     object: Box::new(expr),
                     member,
                 };
+            } else if self.check_contextual_met() {
+                // Functional update: punt met { x: 3 }
+                self.advance(); // consume 'met'
+                self.advance(); // consume '{'
+                let fields = self.named_fields(&TokenType::RightBrace)?;
+                self.consume(&TokenType::RightBrace, "Verwag '}' na velde.")?;
+                expr = Expr::RecordUpdate {
+                    object: Box::new(expr),
+                    fields,
+                };
             } else {
                 break;
             }
@@ -453,7 +607,17 @@ impl Parser {
         Ok(expr)
     }
 
-    fn finish_call(&mut self, callee: Expr) -> Result<Expr, String> {
+    fn finish_call(&mut self, callee: Expr, line: usize) -> Result<Expr, String> {
+        // Named arguments: Punt(x: 1, y: 2)
+        if self.check_plain_name(&TokenType::Colon) {
+            let arguments = self.named_fields(&TokenType::RightParen)?;
+            self.consume(&TokenType::RightParen, "Verwag ')' na argumente.")?;
+            return Ok(Expr::NamedCall {
+                callee: Box::new(callee),
+                arguments,
+            });
+        }
+
         let mut arguments = Vec::new();
 
         if !self.check(&TokenType::RightParen) {
@@ -478,9 +642,39 @@ impl Parser {
         Ok(Expr::Call {
             callee: Box::new(callee),
             arguments,
+            line,
         })
     }
 
+    /// Parse `naam: waarde` pairs separated by commas, up to the closing token
+    fn named_fields(&mut self, close: &TokenType) -> Result<Vec<(String, Expr)>, String> {
+        let mut fields: Vec<(String, Expr)> = Vec::new();
+
+        self.skip_newlines();
+        while !self.check(close) && !self.is_at_end() {
+            let line = self.peek().line;
+            let name = self.consume_identifier("Verwag veld naam.")?;
+            if fields.iter().any(|(existing, _)| *existing == name) {
+                return Err(format!(
+                    "Veld '{}' is meer as een keer gegee. (lyn {})",
+                    name, line
+                ));
+            }
+            self.consume(&TokenType::Colon, "Verwag ':' na veld naam.")?;
+            let value = self.expression()?;
+            fields.push((name, value));
+
+            self.skip_newlines();
+            if !self.check(&TokenType::Comma) {
+                break;
+            }
+            self.advance(); // consume comma
+            self.skip_newlines();
+        }
+
+        Ok(fields)
+    }
+
     fn primary(&mut self) -> Result<Expr, String> {
         if self.check(&TokenType::Waar) {
             self.advance();
@@ -498,12 +692,57 @@ impl Parser {
             return Ok(Expr::Literal(Literal::Number(value)));
         }
 
+        if let TokenType::Integer(i) = &self.peek().token_type {
+            let value = *i;
+            self.advance();
+            return Ok(Expr::Literal(Literal::Integer(value)));
+        }
+
+        if let TokenType::BigInteger(n) = &self.peek().token_type {
+            let value = n.clone();
+            self.advance();
+            return Ok(Expr::Literal(Literal::BigInt(value)));
+        }
+
         if let TokenType::Str(s) = &self.peek().token_type {
             let value = s.clone();
             self.advance();
             return Ok(Expr::Literal(Literal::String(value)));
         }
 
+        if let TokenType::InterpolationStart(s) = &self.peek().token_type {
+            let mut parts = vec![Expr::Literal(Literal::String(s.clone()))];
+            self.advance();
+            loop {
+                // The expression may sit on its own lines in a multi-line string
+                self.skip_newlines();
+                parts.push(self.expression()?);
+                self.skip_newlines();
+                let piece = match &self.peek().token_type {
+                    TokenType::InterpolationMid(s) => Some(s.clone()),
+                    TokenType::InterpolationEnd(s) => {
+                        parts.push(Expr::Literal(Literal::String(s.clone())));
+                        self.advance();
+                        break;
+                    }
+                    _ => None,
+                };
+                match piece {
+                    Some(s) => {
+                        parts.push(Expr::Literal(Literal::String(s)));
+                        self.advance();
+                    }
+                    None => {
+                        return Err(format!(
+                            "Verwag '}}' na uitdrukking in string. (lyn {})",
+                            self.peek().line
+                        ))
+                    }
+                }
+            }
+            return Ok(Expr::Interpolation(parts));
+        }
+
         if let TokenType::Identifier(name) = &self.peek().token_type {
             let name = name.clone();
             self.advance();
@@ -513,6 +752,18 @@ impl Parser {
         if self.check(&TokenType::LeftParen) {
             self.advance();
             let expr = self.expression()?;
+
+            // Tuple literal: (a, b)
+            if self.check(&TokenType::Comma) {
+                let mut elements = vec![expr];
+                while self.check(&TokenType::Comma) {
+                    self.advance(); // consume comma
+                    elements.push(self.expression()?);
+                }
+                self.consume(&TokenType::RightParen, "Verwag ')' na tupel elemente.")?;
+                return Ok(Expr::Tuple(elements));
+            }
+
             self.consume(&TokenType::RightParen, "Verwag ')' na uitdrukking.")?;
             return Ok(Expr::Grouping(Box::new(expr)));
         }
@@ -536,16 +787,40 @@ impl Parser {
             return Ok(Expr::List(elements));
         }
 
+        // Dictionary literal: {"a": 1, "b": 2}
+        if self.check(&TokenType::LeftBrace) {
+            self.advance();
+            let mut entries = Vec::new();
+
+            self.skip_newlines();
+            while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+                let key = self.expression()?;
+                self.consume(&TokenType::Colon, "Verwag ':' na sleutel.")?;
+                let value = self.expression()?;
+                entries.push((key, value));
+
+                self.skip_newlines();
+                if !self.check(&TokenType::Comma) {
+                    break;
+                }
+                self.advance(); // consume comma
+                self.skip_newlines();
+            }
+
+            self.consume(&TokenType::RightBrace, "Verwag '}' na woordeboek elemente.")?;
+            return Ok(Expr::Dict(entries));
+        }
+
         // Lambda expression: fn(params) expr or fn(params) { stmts }
         if self.check(&TokenType::Fn) {
-            self.advance();
-            return self.lambda();
+            let line = self.advance().line;
+            return self.lambda(line);
         }
 
         // Pattern matching expression: pas(value) { ... }
         if self.check(&TokenType::Pas) {
-            self.advance();
-            return self.match_expr();
+            let line = self.advance().line;
+            return self.match_expr(line);
         }
 
         // Inline if expression: as(condition) then_expr anders else_expr
@@ -560,7 +835,7 @@ impl Parser {
         ))
     }
 
-    fn match_expr(&mut self) -> Result<Expr, String> {
+    fn match_expr(&mut self, line: usize) -> Result<Expr, String> {
         self.consume(&TokenType::LeftParen, "Verwag '(' na 'pas'.")?;
         let value = self.expression()?;
         self.consume(&TokenType::RightParen, "Verwag ')' na waarde.")?;
@@ -592,6 +867,7 @@ impl Parser {
         Ok(Expr::Match {
             value: Box::new(value),
             arms,
+            line,
         })
     }
 
@@ -615,12 +891,87 @@ impl Parser {
     }
 
     fn parse_pattern(&mut self) -> Result<Pattern, String> {
+        let first = self.parse_binding_pattern()?;
+
+        // Or-pattern: alternatives separated by 'of', '|' or '||'
+        if !self.check_pattern_separator() {
+            return Ok(first);
+        }
+
+        let mut alternatives = vec![first];
+        while self.check_pattern_separator() {
+            self.advance();
+            alternatives.push(self.parse_binding_pattern()?);
+        }
+
+        Ok(Pattern::Or(alternatives))
+    }
+
+    fn check_pattern_separator(&self) -> bool {
+        self.check(&TokenType::Of) || self.check(&TokenType::Pipe) || self.check(&TokenType::Or)
+    }
+
+    fn parse_binding_pattern(&mut self) -> Result<Pattern, String> {
+        let pattern = self.parse_primary_pattern()?;
+
+        // As-binding: pattern @ name
+        if self.check(&TokenType::At) {
+            self.advance();
+            let name = self.consume_identifier("Verwag naam na '@' in patroon.")?;
+            return Ok(Pattern::Binding {
+                name,
+                pattern: Box::new(pattern),
+            });
+        }
+
+        Ok(pattern)
+    }
+
+    fn parse_primary_pattern(&mut self) -> Result<Pattern, String> {
         // Wildcard: _
         if self.check(&TokenType::Underscore) {
             self.advance();
             return Ok(Pattern::Wildcard);
         }
 
+        // Parenthesised pattern: (Rooi of Groen) @ kleur, or tuple pattern: (a, b)
+        if self.check(&TokenType::LeftParen) {
+            self.advance();
+            let pattern = self.parse_pattern()?;
+
+            if self.check(&TokenType::Comma) {
+                let mut elements = vec![pattern];
+                while self.check(&TokenType::Comma) {
+                    self.advance();
+                    elements.push(self.parse_pattern()?);
+                }
+                self.consume(&TokenType::RightParen, "Verwag ')' na tupel patrone.")?;
+                return Ok(Pattern::Tuple(elements));
+            }
+
+            self.consume(&TokenType::RightParen, "Verwag ')' na patroon.")?;
+            return Ok(pattern);
+        }
+
+        // List pattern: [a, b]
+        if self.check(&TokenType::LeftBracket) {
+            self.advance();
+            let mut elements = Vec::new();
+
+            if !self.check(&TokenType::RightBracket) {
+                loop {
+                    elements.push(self.parse_pattern()?);
+                    if !self.check(&TokenType::Comma) {
+                        break;
+                    }
+                    self.advance();
+                }
+            }
+
+            self.consume(&TokenType::RightBracket, "Verwag ']' na lys patrone.")?;
+            return Ok(Pattern::List(elements));
+        }
+
         // Boolean literals
         if self.check(&TokenType::Waar) {
             self.advance();
@@ -632,11 +983,25 @@ impl Parser {
             return Ok(Pattern::Literal(Literal::Boolean(false)));
         }
 
-        // Number literal
-        if let TokenType::Number(n) = &self.peek().token_type {
-            let value = *n;
-            self.advance();
-            return Ok(Pattern::Literal(Literal::Number(value)));
+        // Number literal, optionally the start of a range: 1..9
+        if let Some(literal) = self.parse_pattern_number()? {
+            if self.check(&TokenType::DotDot) {
+                self.advance();
+                let end = self.parse_pattern_number()?.ok_or_else(|| {
+                    format!("Verwag getal na '..' in reeks-patroon. (lyn {})", self.peek().line)
+                })?;
+                let (low, high) = (Self::number_value(&literal), Self::number_value(&end));
+                if numeric::compare(&low, &high) == Some(Some(Ordering::Greater)) {
+                    return Err(format!(
+                        "Ongeldige reeks-patroon: {} is groter as {}. (lyn {})",
+                        low,
+                        high,
+                        self.peek().line
+                    ));
+                }
+                return Ok(Pattern::Range { start: literal, end });
+            }
+            return Ok(Pattern::Literal(literal));
         }
 
         // String literal
@@ -690,7 +1055,55 @@ impl Parser {
         ))
     }
 
-    fn lambda(&mut self) -> Result<Expr, String> {
+    /// Parses an optionally negated number literal inside a pattern.
+    fn parse_pattern_number(&mut self) -> Result<Option<Literal>, String> {
+        let negative = self.check(&TokenType::Minus);
+        if negative {
+            self.advance();
+        }
+
+        if let TokenType::Number(n) = &self.peek().token_type {
+            let value = *n;
+            self.advance();
+            return Ok(Some(Literal::Number(if negative { -value } else { value })));
+        }
+
+        if let TokenType::Integer(i) = &self.peek().token_type {
+            let value = *i;
+            self.advance();
+            return Ok(Some(Literal::Integer(if negative { -value } else { value })));
+        }
+
+        if let TokenType::BigInteger(n) = &self.peek().token_type {
+            let value = if negative { n.neg() } else { n.clone() };
+            self.advance();
+            // -9223372036854775808 still fits in an i64
+            return Ok(Some(match value.to_i64() {
+                Some(i) => Literal::Integer(i),
+                None => Literal::BigInt(value),
+            }));
+        }
+
+        if negative {
+            return Err(format!(
+                "Verwag getal na '-' in patroon. (lyn {})",
+                self.peek().line
+            ));
+        }
+
+        Ok(None)
+    }
+
+    fn number_value(literal: &Literal) -> Value {
+        match literal {
+            Literal::Integer(i) => Value::Integer(*i),
+            Literal::BigInt(n) => numeric::from_bigint(n.clone()),
+            Literal::Number(n) => Value::Number(*n),
+            _ => Value::Nil,
+        }
+    }
+
+    fn lambda(&mut self, line: usize) -> Result<Expr, String> {
         self.consume(&TokenType::LeftParen, "Verwag '(' na 'fn'.")?;
         let mut params = Vec::new();
 
@@ -702,7 +1115,13 @@ impl Parser {
                         self.peek().line
                     ));
                 }
-                params.push(self.consume_identifier("Verwag parameter naam.")?);
+                // A plain name is always a parameter, even if it starts with a capital
+                if self.check_plain_name(&TokenType::Comma) || self.check_plain_name(&TokenType::RightParen) {
+                    let name = self.consume_identifier("Verwag parameter naam.")?;
+                    params.push(Pattern::Variable(name));
+                } else {
+                    params.push(self.parse_pattern()?);
+                }
 
                 if !self.check(&TokenType::Comma) {
                     break;
@@ -713,8 +1132,8 @@ impl Parser {
 
         self.consume(&TokenType::RightParen, "Verwag ')' na parameters.")?;
 
-        // Check if body is a block or an expression
-        let body = if self.check(&TokenType::LeftBrace) {
+        // Check if body is a block or an expression; fn(x) {"a": x} returns a dictionary
+        let body = if self.check(&TokenType::LeftBrace) && !self.check_dict_literal() {
             self.advance();
             let stmts = self.block()?;
             LambdaBody::Block(stmts)
@@ -723,7 +1142,7 @@ impl Parser {
             LambdaBody::Expr(Box::new(expr))
         };
 
-        Ok(Expr::Lambda { params, body })
+        Ok(Expr::Lambda { params, body, line })
     }
 
     // Helper methods
@@ -750,6 +1169,43 @@ impl Parser {
         std::mem::discriminant(&self.peek().token_type) == std::mem::discriminant(token_type)
     }
 
+    /// Check for an identifier directly followed by the given token
+    fn check_plain_name(&self, next: &TokenType) -> bool {
+        self.check(&TokenType::Identifier(String::new()))
+            && self.current + 1 < self.tokens.len()
+            && std::mem::discriminant(&self.tokens[self.current + 1].token_type)
+                == std::mem::discriminant(next)
+    }
+
+    /// Check for the contextual keyword `met` starting a functional update
+    fn check_contextual_met(&self) -> bool {
+        matches!(&self.peek().token_type, TokenType::Identifier(name) if name == "met")
+            && self.check_plain_name(&TokenType::LeftBrace)
+    }
+
+    /// Check whether the '{' at the current token opens a dictionary rather
+    /// than a block: a ':' follows on its first line, outside any brackets
+    fn check_dict_literal(&self) -> bool {
+        let mut depth = 0;
+        let mut started = false;
+        for token in &self.tokens[self.current + 1..] {
+            match token.token_type {
+                TokenType::Newline if !started => continue,
+                TokenType::LeftParen | TokenType::LeftBracket | TokenType::LeftBrace => depth += 1,
+                TokenType::RightParen | TokenType::RightBracket | TokenType::RightBrace if depth > 0 => depth -= 1,
+                TokenType::Colon if depth == 0 => return true,
+                TokenType::RightParen
+                | TokenType::RightBracket
+                | TokenType::RightBrace
+                | TokenType::Newline
+                | TokenType::Eof => return false,
+                _ => {}
+            }
+            started = true;
+        }
+        false
+    }
+
     fn consume(&mut self, token_type: &TokenType, message: &str) -> Result<&Token, String> {
         if self.check(token_type) {
             Ok(self.advance())
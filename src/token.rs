@@ -1,3 +1,5 @@
+use crate::bignum::BigInt;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     // Keywords (Afrikaans) - Original
@@ -22,9 +24,21 @@ pub enum TokenType {
     Laai,       // import/load module
     Verskaf,    // export/provide
 
+    // Keywords (Afrikaans) - Errors
+    Probeer,    // try
+    Vang,       // catch
+    Gooi,       // throw
+
     // Literals
-    Number(f64),
+    Number(f64),    // float literal: 1.5
+    Integer(i64),   // integer literal: 42
+    BigInteger(BigInt), // integer literal too large for i64
     Str(String),    // string literal
+    // Interpolated string "a {x} b {y} c": InterpolationStart("a "), tokens
+    // of x, InterpolationMid(" b "), tokens of y, InterpolationEnd(" c")
+    InterpolationStart(String),
+    InterpolationMid(String),
+    InterpolationEnd(String),
     Identifier(String),
 
     // Operators
@@ -32,6 +46,7 @@ pub enum TokenType {
     Minus,          // -
     Star,           // *
     Slash,          // /
+    TildeSlash,     // ~/ (integer division)
     Percent,        // %
     Equal,          // =
     EqualEqual,     // ==
@@ -43,6 +58,13 @@ pub enum TokenType {
     GreaterEqual,   // >=
     And,            // &&
     Or,             // ||
+    Ampersand,      // & (bitwise and)
+    Pipe,           // | (bitwise or)
+    Caret,          // ^ (bitwise xor)
+    Tilde,          // ~ (bitwise not)
+    LessLess,       // <<
+    GreaterGreater, // >>
+    Question,       // ? (unwrap Sommige/Ok or return the failure)
 
     // Punctuation
     LeftParen,      // (
@@ -52,7 +74,10 @@ pub enum TokenType {
     LeftBracket,    // [
     RightBracket,   // ]
     Comma,          // ,
+    Colon,          // : (named fields)
     Dot,            // . (member access)
+    DotDot,         // .. (range pattern)
+    At,             // @ (as-binding in patterns)
     Underscore,     // _ (wildcard pattern)
     Arrow,          // -> (pipe operator)
     FatArrow,       // => (pattern matching)
@@ -1,9 +1,13 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::rc::Rc;
 
+use crate::bignum::{BigInt, Rational};
 use crate::bytecode::Chunk;
+use crate::numeric;
+use crate::vm::VM;
 
 /// Represents a compiled function
 #[derive(Debug, Clone)]
@@ -40,12 +44,45 @@ pub struct Closure {
     pub upvalues: Vec<Rc<RefCell<Upvalue>>>,
 }
 
+/// Signature of a native function. The VM is passed in so natives can call
+/// back into Arkaan functions with `call_value`.
+pub type NativeFn = Rc<dyn Fn(&mut VM, &[Value]) -> Result<Value, String>>;
+
+/// Number of arguments a native function accepts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arity {
+    Exact(usize),
+    /// From the first to the second number, inclusive
+    Range(usize, usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    pub fn accepts(&self, count: usize) -> bool {
+        match *self {
+            Arity::Exact(n) => count == n,
+            Arity::Range(min, max) => (min..=max).contains(&count),
+            Arity::AtLeast(min) => count >= min,
+        }
+    }
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arity::Exact(n) => write!(f, "{}", n),
+            Arity::Range(min, max) => write!(f, "{} tot {}", min, max),
+            Arity::AtLeast(min) => write!(f, "ten minste {}", min),
+        }
+    }
+}
+
 /// Represents a native (built-in) function
 #[derive(Clone)]
 pub struct NativeFunction {
     pub name: String,
-    pub arity: usize,
-    pub func: fn(&[Value]) -> Result<Value, String>,
+    pub arity: Arity,
+    pub func: NativeFn,
 }
 
 impl std::fmt::Debug for NativeFunction {
@@ -59,7 +96,15 @@ impl std::fmt::Debug for NativeFunction {
 pub struct TypeConstructorDef {
     pub type_name: String,          // Name of the type (e.g., "Opsie")
     pub constructor_name: String,   // Name of the constructor (e.g., "Sommige")
+    pub tag: usize,                 // Position in the type declaration (orders constructors)
     pub arity: usize,               // Number of fields
+    pub field_names: Rc<Vec<String>>, // Declared field names (e.g., ["waarde"])
+}
+
+impl TypeConstructorDef {
+    pub fn field_index(&self, name: &str) -> Option<usize> {
+        self.field_names.iter().position(|field| field == name)
+    }
 }
 
 /// An instance of an algebraic data type
@@ -67,7 +112,15 @@ pub struct TypeConstructorDef {
 pub struct AdtInstance {
     pub type_name: String,          // Name of the type
     pub constructor_name: String,   // Name of the constructor used
+    pub tag: usize,                 // Position of the constructor in the type declaration
     pub fields: Vec<Value>,         // Field values
+    pub field_names: Rc<Vec<String>>, // Field names, shared with the constructor
+}
+
+impl AdtInstance {
+    pub fn field_index(&self, name: &str) -> Option<usize> {
+        self.field_names.iter().position(|field| field == name)
+    }
 }
 
 /// A loaded module with its exported symbols
@@ -78,9 +131,147 @@ pub struct Module {
     pub exports: HashMap<String, Value>,    // Exported symbols
 }
 
+/// A hashable form of a value, used for dictionary keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HashKey {
+    Integer(i64),
+    BigInt(BigInt),
+    Rational(Rational),
+    Number(u64),
+    Boolean(bool),
+    String(Rc<String>),
+    Nil,
+    List(Vec<HashKey>),
+    Tuple(Vec<HashKey>),
+    Adt(String, String, Vec<HashKey>),
+}
+
+/// An immutable dictionary that keeps its keys in insertion order
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    entries: Vec<(Value, Value)>,
+    index: HashMap<HashKey, usize>,
+}
+
+impl Dictionary {
+    pub fn new() -> Self {
+        Dictionary::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &(Value, Value)> {
+        self.entries.iter()
+    }
+
+    pub fn get(&self, key: &Value) -> Result<Option<&Value>, String> {
+        let hash_key = key.hash_key()?;
+        Ok(self.index.get(&hash_key).map(|&i| &self.entries[i].1))
+    }
+
+    pub fn contains(&self, key: &Value) -> Result<bool, String> {
+        Ok(self.index.contains_key(&key.hash_key()?))
+    }
+
+    /// Insert or replace a key; a replaced key keeps its original position
+    pub fn insert(&mut self, key: Value, value: Value) -> Result<(), String> {
+        let hash_key = key.hash_key()?;
+        if let Some(&i) = self.index.get(&hash_key) {
+            self.entries[i].1 = value;
+        } else {
+            self.index.insert(hash_key, self.entries.len());
+            self.entries.push((key, value));
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &Value) -> Result<(), String> {
+        if let Some(i) = self.index.remove(&key.hash_key()?) {
+            self.entries.remove(i);
+            for slot in self.index.values_mut() {
+                if *slot > i {
+                    *slot -= 1;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Dictionary {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self.entries.iter().all(|(key, value)| {
+                matches!(other.get(key), Ok(Some(other_value)) if other_value == value)
+            })
+    }
+}
+
+/// An immutable set that keeps its elements in insertion order
+#[derive(Debug, Clone, Default)]
+pub struct Set {
+    items: Vec<Value>,
+    keys: HashSet<HashKey>,
+}
+
+impl Set {
+    pub fn new() -> Self {
+        Set::default()
+    }
+
+    /// Build a set from values, dropping duplicates after their first occurrence
+    pub fn from_values(values: impl IntoIterator<Item = Value>) -> Result<Self, String> {
+        let mut set = Set::new();
+        for value in values {
+            set.insert(value)?;
+        }
+        Ok(set)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn items(&self) -> &[Value] {
+        &self.items
+    }
+
+    pub fn contains(&self, value: &Value) -> Result<bool, String> {
+        Ok(self.keys.contains(&value.hash_key()?))
+    }
+
+    pub fn insert(&mut self, value: Value) -> Result<(), String> {
+        if self.keys.insert(value.hash_key()?) {
+            self.items.push(value);
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Set {
+    fn eq(&self, other: &Self) -> bool {
+        self.keys == other.keys
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Value {
     Number(f64),
+    Integer(i64),
+    // Integer too large for i64 (integer arithmetic grows into this on overflow)
+    BigInt(Rc<BigInt>),
+    // Exact fraction, never with denominator 1
+    Rational(Rc<Rational>),
     Boolean(bool),
     String(Rc<String>),
     Nil,
@@ -94,14 +285,78 @@ pub enum Value {
     Adt(Rc<AdtInstance>),
     // Loaded module
     Module(Rc<Module>),
+    // Immutable tuple: (a, b)
+    Tuple(Rc<Vec<Value>>),
+    // Immutable dictionary: {"a": 1}
+    Woordeboek(Rc<Dictionary>),
+    // Immutable set: versameling([1, 2])
+    Versameling(Rc<Set>),
 }
 
 impl Value {
+    /// Name of the value's type, for error messages
+    pub fn type_name(&self) -> String {
+        match self {
+            Value::Number(_) | Value::Integer(_) | Value::BigInt(_) | Value::Rational(_) => "nommer".to_string(),
+            Value::Boolean(_) => "boolean".to_string(),
+            Value::String(_) => "string".to_string(),
+            Value::Nil => "nil".to_string(),
+            Value::List(_) => "lys".to_string(),
+            Value::Function(_) | Value::Closure(_) | Value::NativeFunction(_) => "funksie".to_string(),
+            Value::TypeConstructor(tc) => tc.type_name.clone(),
+            Value::Adt(adt) => adt.type_name.clone(),
+            Value::Module(_) => "module".to_string(),
+            Value::Tuple(_) => "tupel".to_string(),
+            Value::Woordeboek(_) => "woordeboek".to_string(),
+            Value::Versameling(_) => "versameling".to_string(),
+        }
+    }
+
+    /// Order two values: numbers by value, strings by code point, lists and
+    /// tuples element by element, and values of one ADT by constructor
+    /// declaration order and then fields. Ok(None) when a NaN is involved;
+    /// the error message has no full stop so callers can add context.
+    pub fn compare(&self, other: &Value) -> Result<Option<Ordering>, String> {
+        if let Some(ordering) = numeric::compare(self, other) {
+            return Ok(ordering);
+        }
+        match (self, other) {
+            (Value::String(a), Value::String(b)) => Ok(Some(a.as_str().cmp(b.as_str()))),
+            (Value::List(a), Value::List(b)) | (Value::Tuple(a), Value::Tuple(b)) => compare_sequences(a, b),
+            _ => match (self.adt_parts(), other.adt_parts()) {
+                (Some((type_a, tag_a, fields_a)), Some((type_b, tag_b, fields_b))) if type_a == type_b => {
+                    match tag_a.cmp(&tag_b) {
+                        Ordering::Equal => compare_sequences(fields_a, fields_b),
+                        ordering => Ok(Some(ordering)),
+                    }
+                }
+                _ => Err(format!(
+                    "Kan nie {} met {} vergelyk nie",
+                    self.type_name(),
+                    other.type_name()
+                )),
+            },
+        }
+    }
+
+    /// Type name, constructor tag and fields of an ADT value. A unit
+    /// constructor used without a call counts as a value without fields.
+    fn adt_parts(&self) -> Option<(&str, usize, &[Value])> {
+        match self {
+            Value::Adt(adt) => Some((&adt.type_name, adt.tag, &adt.fields)),
+            Value::TypeConstructor(tc) if tc.arity == 0 => Some((&tc.type_name, tc.tag, &[])),
+            _ => None,
+        }
+    }
+
     pub fn is_truthy(&self) -> bool {
         match self {
             Value::Nil => false,
             Value::Boolean(b) => *b,
             Value::Number(n) => *n != 0.0,
+            Value::Integer(i) => *i != 0,
+            // Normalised big numbers are never zero
+            Value::BigInt(_) | Value::Rational(_) => true,
             Value::String(s) => !s.is_empty(),
             Value::List(l) => !l.is_empty(),
             Value::Function(_) => true,
@@ -110,6 +365,60 @@ impl Value {
             Value::TypeConstructor(_) => true,
             Value::Adt(_) => true,
             Value::Module(_) => true,
+            Value::Tuple(_) => true,
+            Value::Woordeboek(d) => !d.is_empty(),
+            Value::Versameling(s) => !s.is_empty(),
+        }
+    }
+
+    /// Numeric value as a float, for mixed integer/float arithmetic
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            Value::Integer(i) => Some(*i as f64),
+            Value::BigInt(n) => Some(n.to_f64()),
+            Value::Rational(r) => Some(r.to_f64()),
+            _ => None,
+        }
+    }
+
+    /// Convert a value to a dictionary key. Numbers, strings, booleans, nil,
+    /// and lists, tuples or ADTs made of those can be keys.
+    pub fn hash_key(&self) -> Result<HashKey, String> {
+        match self {
+            Value::Number(n) => {
+                if n.is_nan() {
+                    return Err("NaN kan nie as sleutel gebruik word nie.".to_string());
+                }
+                // Floats equal the exact number they represent, so they must hash the same
+                match numeric::exact_from_f64(*n) {
+                    Some(exact) => exact.hash_key(),
+                    None => Ok(HashKey::Number(n.to_bits())),
+                }
+            }
+            Value::Integer(i) => Ok(HashKey::Integer(*i)),
+            Value::BigInt(n) => Ok(HashKey::BigInt(n.as_ref().clone())),
+            Value::Rational(r) => Ok(HashKey::Rational(r.as_ref().clone())),
+            Value::Boolean(b) => Ok(HashKey::Boolean(*b)),
+            Value::String(s) => Ok(HashKey::String(Rc::clone(s))),
+            Value::Nil => Ok(HashKey::Nil),
+            Value::List(items) => Ok(HashKey::List(
+                items.iter().map(Value::hash_key).collect::<Result<_, _>>()?,
+            )),
+            Value::Tuple(items) => Ok(HashKey::Tuple(
+                items.iter().map(Value::hash_key).collect::<Result<_, _>>()?,
+            )),
+            Value::Adt(adt) => Ok(HashKey::Adt(
+                adt.type_name.clone(),
+                adt.constructor_name.clone(),
+                adt.fields.iter().map(Value::hash_key).collect::<Result<_, _>>()?,
+            )),
+            Value::TypeConstructor(tc) if tc.arity == 0 => Ok(HashKey::Adt(
+                tc.type_name.clone(),
+                tc.constructor_name.clone(),
+                Vec::new(),
+            )),
+            _ => Err(format!("Waarde '{}' kan nie as sleutel gebruik word nie.", self)),
         }
     }
 }
@@ -118,12 +427,15 @@ impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Value::Number(n) => {
-                if n.fract() == 0.0 {
+                if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
                     write!(f, "{}", *n as i64)
                 } else {
                     write!(f, "{}", n)
                 }
             }
+            Value::Integer(i) => write!(f, "{}", i),
+            Value::BigInt(n) => write!(f, "{}", n),
+            Value::Rational(r) => write!(f, "{}", r),
             Value::Boolean(b) => {
                 if *b {
                     write!(f, "waar")
@@ -146,6 +458,7 @@ impl fmt::Display for Value {
             Value::Function(func) => write!(f, "<funksie {}>", func.name),
             Value::Closure(closure) => write!(f, "<funksie {}>", closure.function.name),
             Value::NativeFunction(nf) => write!(f, "<ingeboude {}>", nf.name),
+            Value::TypeConstructor(tc) if tc.arity == 0 => write!(f, "{}", tc.constructor_name),
             Value::TypeConstructor(tc) => write!(f, "<konstruktor {}.{}>", tc.type_name, tc.constructor_name),
             Value::Adt(adt) => {
                 write!(f, "{}", adt.constructor_name)?;
@@ -162,14 +475,55 @@ impl fmt::Display for Value {
                 Ok(())
             }
             Value::Module(m) => write!(f, "<module {}>", m.name),
+            Value::Tuple(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+            Value::Woordeboek(d) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in d.entries().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+            Value::Versameling(set) => {
+                write!(f, "#{{")?;
+                for (i, item) in set.items().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "}}")
+            }
         }
     }
 }
 
+/// Lexicographic order: the first unequal pair decides, then the length
+fn compare_sequences(a: &[Value], b: &[Value]) -> Result<Option<Ordering>, String> {
+    for (x, y) in a.iter().zip(b.iter()) {
+        match x.compare(y)? {
+            Some(Ordering::Equal) => {}
+            ordering => return Ok(ordering),
+        }
+    }
+    Ok(Some(a.len().cmp(&b.len())))
+}
+
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Value::Number(a), Value::Number(b)) => a == b,
+            _ if self.as_f64().is_some() && other.as_f64().is_some() => numeric::numbers_equal(self, other),
             (Value::Boolean(a), Value::Boolean(b)) => a == b,
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Nil, Value::Nil) => true,
@@ -177,14 +531,23 @@ impl PartialEq for Value {
             (Value::Function(a), Value::Function(b)) => Rc::ptr_eq(a, b),
             (Value::Closure(a), Value::Closure(b)) => Rc::ptr_eq(a, b),
             (Value::NativeFunction(a), Value::NativeFunction(b)) => Rc::ptr_eq(a, b),
-            (Value::TypeConstructor(a), Value::TypeConstructor(b)) => Rc::ptr_eq(a, b),
+            (Value::TypeConstructor(a), Value::TypeConstructor(b)) => {
+                a.type_name == b.type_name && a.constructor_name == b.constructor_name
+            }
             (Value::Adt(a), Value::Adt(b)) => {
                 a.type_name == b.type_name
                     && a.constructor_name == b.constructor_name
                     && a.fields == b.fields
             }
             (Value::Module(a), Value::Module(b)) => Rc::ptr_eq(a, b),
-            _ => false,
+            (Value::Tuple(a), Value::Tuple(b)) => a == b,
+            (Value::Woordeboek(a), Value::Woordeboek(b)) => a == b,
+            (Value::Versameling(a), Value::Versameling(b)) => a == b,
+            // A unit constructor used without a call equals its zero-field instance
+            _ => match (self.adt_parts(), other.adt_parts()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
         }
     }
 }
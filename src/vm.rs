@@ -1,13 +1,25 @@
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::time::Instant;
 
 use crate::bytecode::{Chunk, OpCode};
 use crate::compiler::Compiler;
-use crate::lexer::Lexer;
+use crate::error::{Error, ErrorKind, Failure};
+use crate::json;
+use crate::lexer::{scan_number, Lexer, NumberLiteral};
+use crate::limits::{Limits, LIMIT_ERROR_PREFIX};
+use crate::bignum::BigInt;
+use crate::numeric;
 use crate::parser::Parser;
-use crate::value::{AdtInstance, Closure, Function, Module, NativeFunction, TypeConstructorDef, Upvalue, UpvalueLocation, Value};
+use crate::random::Random;
+use crate::value::{AdtInstance, Arity, Closure, Dictionary, HashKey, Set, Function, Module, NativeFn, NativeFunction, TypeConstructorDef, Upvalue, UpvalueLocation, Value};
+
+/// How many instructions run between reads of the clock for the timeout
+const CLOCK_CHECK_INTERVAL: u32 = 1024;
 
 #[derive(Debug, Clone)]
 struct CallFrame {
@@ -17,6 +29,15 @@ struct CallFrame {
     slots_start: usize, // Where this frame's locals start on the stack
 }
 
+/// An active `probeer` block in one dispatch loop
+struct Handler {
+    catch_ip: usize,
+    stack_len: usize,
+    frames_len: usize,
+    // Chunk, slots start and closure to resume in; None in the main chunk
+    function: Option<(Rc<Chunk>, usize, Option<Rc<Closure>>)>,
+}
+
 pub struct VM {
     chunk: Chunk,              // The main/script chunk
     functions: Vec<Rc<Chunk>>, // Compiled function chunks (Rc for cheap cloning)
@@ -26,6 +47,22 @@ pub struct VM {
     open_upvalues: Vec<Rc<RefCell<Upvalue>>>,  // Open upvalues pointing to stack
     module_cache: HashMap<PathBuf, Rc<Module>>,  // Cached modules
     current_file: Option<PathBuf>,               // Current file path for relative imports
+    exact_division: bool,                        // '/' on integers gives fractions (presiese_deling)
+    host_functions: Vec<Rc<NativeFunction>>,     // Natives registered by an embedding host
+    output: Box<dyn Write>,                      // Where druk and skryf write
+    error_output: Box<dyn Write>,                // Where druk_fout writes
+    input: Box<dyn BufRead>,                     // Where lees_lyn and lees_alles read
+    limits: Limits,
+    executed: u64,                               // Instructions executed in the current run
+    deadline: Option<Instant>,                   // When the current run times out
+    until_clock_check: u32,                      // Instructions left before the deadline is checked
+    limit_exceeded: bool,                        // Set when a sandbox limit stops the run
+    thrown: Option<Value>,                       // Value raised by 'gooi' that is unwinding
+    error_kind: Option<ErrorKind>,               // Kind of the built-in error that is unwinding
+    line: usize,                                 // Source line of the current instruction
+    arguments: Vec<String>,                      // Script arguments returned by argumente()
+    exit_code: Option<i32>,                      // Set by verlaat() while the run unwinds
+    random: Random,                              // Generator behind willekeurig, reseeded by saad()
 }
 
 impl VM {
@@ -39,6 +76,22 @@ impl VM {
             open_upvalues: Vec::new(),
             module_cache: HashMap::new(),
             current_file: None,
+            exact_division: false,
+            host_functions: Vec::new(),
+            output: Box::new(io::stdout()),
+            error_output: Box::new(io::stderr()),
+            input: Box::new(BufReader::new(io::stdin())),
+            limits: Limits::default(),
+            executed: 0,
+            deadline: None,
+            until_clock_check: CLOCK_CHECK_INTERVAL,
+            limit_exceeded: false,
+            thrown: None,
+            error_kind: None,
+            line: 0,
+            arguments: Vec::new(),
+            exit_code: None,
+            random: Random::from_time(),
         };
         vm.define_natives();
         vm
@@ -48,7 +101,8 @@ impl VM {
         self.current_file = Some(path);
     }
 
-    fn resolve_module_path(&self, path: &str) -> Result<PathBuf, String> {
+    /// Resolve a path relative to the current file's directory
+    fn resolve_path(&self, path: &str) -> Result<PathBuf, String> {
         let path = Path::new(path);
 
         if path.is_absolute() {
@@ -64,8 +118,12 @@ impl VM {
     }
 
     fn load_module(&mut self, path: &str, alias: &str) -> Result<Value, String> {
+        if !self.limits.allow_imports {
+            return Err(self.fail(ErrorKind::Module, format!("Kan nie module '{}' laai nie: laai is hier nie toegelaat nie.", path)));
+        }
+
         // Resolve the path relative to current file
-        let resolved_path = self.resolve_module_path(path)?;
+        let resolved_path = self.resolve_path(path)?;
 
         // Check cache first
         if let Some(cached) = self.module_cache.get(&resolved_path) {
@@ -74,47 +132,49 @@ impl VM {
 
         // Read the source file
         let source = std::fs::read_to_string(&resolved_path)
-            .map_err(|e| format!("Kon nie module '{}' laai nie: {}", path, e))?;
-
-        // Save current state
-        let old_file = self.current_file.take();
-        self.current_file = Some(resolved_path.clone());
+            .map_err(|e| self.fail(ErrorKind::Module, format!("Kon nie module '{}' laai nie: {}", path, e)))?;
 
         // Compile the module
         let mut lexer = Lexer::new(&source);
-        let tokens = lexer.scan_tokens()?;
+        let tokens = lexer.scan_tokens().map_err(|e| self.fail(ErrorKind::Module, e))?;
 
         let mut parser = Parser::new(tokens);
-        let statements = parser.parse()?;
+        let statements = parser.parse().map_err(|e| self.fail(ErrorKind::Module, e))?;
 
         let mut compiler = Compiler::new();
-        let (chunk, functions) = compiler.compile(statements)?;
+        let (chunk, functions) = compiler.compile(statements).map_err(|e| self.fail(ErrorKind::Module, e))?;
         let exports = compiler.get_exports().clone();
 
-        // Execute the module to populate globals
         // Save current VM state
+        let old_file = self.current_file.replace(resolved_path.clone());
         let old_globals = std::mem::take(&mut self.globals);
         let old_functions = std::mem::replace(&mut self.functions, functions);
         let old_stack = std::mem::take(&mut self.stack);
         let old_frames = std::mem::take(&mut self.frames);
+        let old_upvalues = std::mem::take(&mut self.open_upvalues);
 
         // Re-add native functions for the module
         self.define_natives();
+        for native in self.host_functions.clone() {
+            self.globals.insert(native.name.clone(), Value::NativeFunction(native));
+        }
 
         // Run the module's initialization code
-        self.run_chunk(&chunk)?;
+        let result = self.run_chunk(&chunk);
 
         // Capture the module's globals
         let module_globals = std::mem::take(&mut self.globals);
 
-        // Restore original state
+        // Restore original state, also when the module failed, so a caught
+        // error leaves the importing program as it was
+        self.close_upvalues(0);
         self.globals = old_globals;
         self.functions = old_functions;
         self.stack = old_stack;
         self.frames = old_frames;
-
-        // Restore current file
+        self.open_upvalues = old_upvalues;
         self.current_file = old_file;
+        result?;
 
         // Extract only exported symbols
         let mut exported_values = HashMap::new();
@@ -143,208 +203,1797 @@ impl VM {
             String::from("lengte"),
             Value::NativeFunction(Rc::new(NativeFunction {
                 name: String::from("lengte"),
-                arity: 1,
-                func: |args| {
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
                     match &args[0] {
-                        Value::List(items) => Ok(Value::Number(items.len() as f64)),
-                        Value::String(s) => Ok(Value::Number(s.chars().count() as f64)),
-                        _ => Err("lengte() verwag 'n lys of string.".to_string()),
+                        Value::List(items) => Ok(Value::Integer(items.len() as i64)),
+                        Value::String(s) => Ok(Value::Integer(s.chars().count() as i64)),
+                        Value::Woordeboek(d) => Ok(Value::Integer(d.len() as i64)),
+                        Value::Versameling(set) => Ok(Value::Integer(set.len() as i64)),
+                        _ => Err(vm.fail(ErrorKind::Type, "lengte() verwag 'n lys, string, woordeboek of versameling.".to_string())),
                     }
-                },
+                }),
+            })),
+        );
+
+        // kop(lys) - returns the first element of a list
+        self.globals.insert(
+            String::from("kop"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("kop"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(items) => {
+                            if items.is_empty() {
+                                Err(vm.fail(ErrorKind::EmptyList, "Kan nie kop van leë lys kry nie.".to_string()))
+                            } else {
+                                Ok(items[0].clone())
+                            }
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "kop() verwag 'n lys.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // stert(lys) - returns all but the first element of a list
+        self.globals.insert(
+            String::from("stert"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("stert"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(items) => {
+                            if items.is_empty() {
+                                Err(vm.fail(ErrorKind::EmptyList, "Kan nie stert van leë lys kry nie.".to_string()))
+                            } else {
+                                let tail: Vec<Value> = items[1..].to_vec();
+                                Ok(Value::List(Rc::new(tail)))
+                            }
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "stert() verwag 'n lys.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // leeg(lys) - returns true if list is empty
+        self.globals.insert(
+            String::from("leeg"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("leeg"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(items) => Ok(Value::Boolean(items.is_empty())),
+                        Value::String(s) => Ok(Value::Boolean(s.is_empty())),
+                        Value::Woordeboek(d) => Ok(Value::Boolean(d.is_empty())),
+                        Value::Versameling(set) => Ok(Value::Boolean(set.is_empty())),
+                        _ => Err(vm.fail(ErrorKind::Type, "leeg() verwag 'n lys, string, woordeboek of versameling.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // voeg_by(element, lys) - prepends element to list (cons)
+        self.globals.insert(
+            String::from("voeg_by"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("voeg_by"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match &args[1] {
+                        Value::List(items) => {
+                            let mut new_list = vec![args[0].clone()];
+                            new_list.extend(items.iter().cloned());
+                            Ok(Value::List(Rc::new(new_list)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "voeg_by() verwag 'n lys as tweede argument.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // heg_aan(lys, element) - appends element to list
+        self.globals.insert(
+            String::from("heg_aan"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("heg_aan"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(items) => {
+                            let mut new_list = items.as_ref().clone();
+                            new_list.push(args[1].clone());
+                            Ok(Value::List(Rc::new(new_list)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "heg_aan() verwag 'n lys as eerste argument.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // ketting(lys1, lys2, ...) - concatenates lists
+        self.globals.insert(
+            String::from("ketting"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("ketting"),
+                arity: Arity::AtLeast(1),
+                func: Rc::new(|vm, args| {
+                    let mut size = 0;
+                    for arg in args {
+                        match arg {
+                            Value::List(items) => size += items.len(),
+                            _ => return Err(vm.fail(ErrorKind::Type, "ketting() verwag lyste.".to_string())),
+                        }
+                    }
+                    vm.check_size(size)?;
+                    let mut new_list = Vec::with_capacity(size);
+                    for arg in args {
+                        if let Value::List(items) = arg {
+                            new_list.extend(items.iter().cloned());
+                        }
+                    }
+                    Ok(Value::List(Rc::new(new_list)))
+                }),
+            })),
+        );
+
+        // omgekeer(lys) - reverses a list
+        self.globals.insert(
+            String::from("omgekeer"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("omgekeer"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(items) => {
+                            let reversed: Vec<Value> = items.iter().rev().cloned().collect();
+                            Ok(Value::List(Rc::new(reversed)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "omgekeer() verwag 'n lys.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // sleutels(woordeboek) - returns the keys of a dictionary as a list
+        self.globals.insert(
+            String::from("sleutels"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("sleutels"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::Woordeboek(d) => {
+                            let keys: Vec<Value> = d.entries().map(|(k, _)| k.clone()).collect();
+                            Ok(Value::List(Rc::new(keys)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "sleutels() verwag 'n woordeboek.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // waardes(woordeboek) - returns the values of a dictionary as a list
+        self.globals.insert(
+            String::from("waardes"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("waardes"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::Woordeboek(d) => {
+                            let values: Vec<Value> = d.entries().map(|(_, v)| v.clone()).collect();
+                            Ok(Value::List(Rc::new(values)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "waardes() verwag 'n woordeboek.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // kry(woordeboek, sleutel, verstek) - looks up a key, or returns the default
+        self.globals.insert(
+            String::from("kry"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("kry"),
+                arity: Arity::Exact(3),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::Woordeboek(d) => {
+                            Ok(d.get(&args[1])?.cloned().unwrap_or_else(|| args[2].clone()))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "kry() verwag 'n woordeboek as eerste argument.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // met(woordeboek, sleutel, waarde) - returns a copy with the key set
+        self.globals.insert(
+            String::from("met"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("met"),
+                arity: Arity::Exact(3),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::Woordeboek(d) => {
+                            let mut new_dict = d.as_ref().clone();
+                            new_dict.insert(args[1].clone(), args[2].clone()).map_err(|e| vm.fail(ErrorKind::Type, e))?;
+                            Ok(Value::Woordeboek(Rc::new(new_dict)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "met() verwag 'n woordeboek as eerste argument.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // sonder(woordeboek, sleutel) - returns a copy without the key
+        self.globals.insert(
+            String::from("sonder"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("sonder"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::Woordeboek(d) => {
+                            let mut new_dict = d.as_ref().clone();
+                            new_dict.remove(&args[1]).map_err(|e| vm.fail(ErrorKind::Type, e))?;
+                            Ok(Value::Woordeboek(Rc::new(new_dict)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "sonder() verwag 'n woordeboek as eerste argument.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // bevat(versameling, waarde) - key in dictionary, element in set or list, or substring
+        self.globals.insert(
+            String::from("bevat"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("bevat"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::Woordeboek(d), key) => Ok(Value::Boolean(d.contains(key)?)),
+                        (Value::Versameling(set), value) => Ok(Value::Boolean(set.contains(value)?)),
+                        (Value::List(items), value) => Ok(Value::Boolean(items.contains(value))),
+                        (Value::String(s), Value::String(sub)) => Ok(Value::Boolean(s.contains(sub.as_str()))),
+                        _ => Err(vm.fail(ErrorKind::Type, "bevat() verwag 'n woordeboek, versameling, lys of string.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // heel(waarde) - converts a number or numeric string to an integer (truncates)
+        self.globals.insert(
+            String::from("heel"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("heel"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    let n = match &args[0] {
+                        Value::Integer(_) | Value::BigInt(_) => return Ok(args[0].clone()),
+                        Value::Rational(r) => return Ok(numeric::from_bigint(r.trunc())),
+                        Value::Number(n) => *n,
+                        Value::String(s) => {
+                            if let Ok(i) = s.trim().parse::<i64>() {
+                                return Ok(Value::Integer(i));
+                            }
+                            s.trim()
+                                .parse::<f64>()
+                                .map_err(|_| vm.fail(ErrorKind::Value, format!("heel() kan nie '{}' omskakel nie.", s)))?
+                        }
+                        _ => return Err(vm.fail(ErrorKind::Type, "heel() verwag 'n nommer of string.".to_string())),
+                    };
+                    BigInt::from_f64(n.trunc())
+                        .map(numeric::from_bigint)
+                        .ok_or_else(|| format!("heel() kan nie {} as heelgetal voorstel nie.", n))
+                }),
+            })),
+        );
+
+        // desimaal(waarde) - converts a number or numeric string to a float
+        self.globals.insert(
+            String::from("desimaal"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("desimaal"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::Integer(_) | Value::BigInt(_) | Value::Rational(_) | Value::Number(_) => {
+                            Ok(Value::Number(args[0].as_f64().unwrap()))
+                        }
+                        Value::String(s) => s
+                            .trim()
+                            .parse::<f64>()
+                            .map(Value::Number)
+                            .map_err(|_| vm.fail(ErrorKind::Value, format!("desimaal() kan nie '{}' omskakel nie.", s))),
+                        _ => Err(vm.fail(ErrorKind::Type, "desimaal() verwag 'n nommer of string.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // breuk(teller, noemer) - exact fraction, whatever the division mode
+        self.globals.insert(
+            String::from("breuk"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("breuk"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    if !numeric::is_exact(&args[0]) || !numeric::is_exact(&args[1]) {
+                        return Err(vm.fail(ErrorKind::Type, "breuk() verwag heelgetalle of breuke.".to_string()));
+                    }
+                    numeric::arithmetic(&OpCode::Divide, &args[0], &args[1], true).map_err(|(kind, message)| vm.fail(kind, message))
+                }),
+            })),
+        );
+
+        // presiese_deling(aan) - makes '/' on integers give exact fractions
+        self.globals.insert(
+            String::from("presiese_deling"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("presiese_deling"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    vm.exact_division = args[0].is_truthy();
+                    Ok(Value::Nil)
+                }),
+            })),
+        );
+
+        // Math constants
+        self.globals.insert(String::from("PI"), Value::Number(std::f64::consts::PI));
+        self.globals.insert(String::from("E"), Value::Number(std::f64::consts::E));
+
+        // wortel(x) - square root
+        self.globals.insert(
+            String::from("wortel"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("wortel"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    let x = float_arg(vm, &args[0], "wortel")?;
+                    if x < 0.0 {
+                        return Err(vm.fail(ErrorKind::Value, format!("wortel() verwag 'n nie-negatiewe nommer, nie {} nie.", args[0])));
+                    }
+                    Ok(Value::Number(x.sqrt()))
+                }),
+            })),
+        );
+
+        // mag(basis, eksponent) - exact for integer exponents of exact numbers
+        self.globals.insert(
+            String::from("mag"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("mag"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    if numeric::is_exact(&args[0]) {
+                        vm.check_number_size(numeric::power_bits(&args[0], &args[1]))?;
+                    }
+                    numeric::power(&args[0], &args[1], vm.exact_division).map_err(|(kind, message)| vm.fail(kind, message))
+                }),
+            })),
+        );
+
+        // abs(x) - absolute value
+        self.globals.insert(
+            String::from("abs"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("abs"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| numeric::abs(&args[0]).map_err(|(kind, message)| vm.fail(kind, message))),
+            })),
+        );
+
+        // vloer(x), plafon(x), rond(x) - round down, up, or to the nearest integer
+        let roundings = [
+            ("vloer", numeric::Rounding::Floor),
+            ("plafon", numeric::Rounding::Ceil),
+            ("rond", numeric::Rounding::Nearest),
+        ];
+        for (name, rounding) in roundings {
+            self.globals.insert(
+                String::from(name),
+                Value::NativeFunction(Rc::new(NativeFunction {
+                    name: String::from(name),
+                    arity: Arity::Exact(1),
+                    func: Rc::new(move |vm, args| {
+                        numeric::round(&args[0], rounding, name).map_err(|(kind, message)| vm.fail(kind, message))
+                    }),
+                })),
+            );
+        }
+
+        // sin(x), cos(x), tan(x), exp(x) - angles are in radians
+        let float_functions = [("sin", f64::sin as fn(f64) -> f64), ("cos", f64::cos), ("tan", f64::tan), ("exp", f64::exp)];
+        for (name, func) in float_functions {
+            self.globals.insert(
+                String::from(name),
+                Value::NativeFunction(Rc::new(NativeFunction {
+                    name: String::from(name),
+                    arity: Arity::Exact(1),
+                    func: Rc::new(move |vm, args| Ok(Value::Number(func(float_arg(vm, &args[0], name)?)))),
+                })),
+            );
+        }
+
+        // atan2(y, x) - angle of the point (x, y) in radians
+        self.globals.insert(
+            String::from("atan2"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("atan2"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let y = float_arg(vm, &args[0], "atan2")?;
+                    let x = float_arg(vm, &args[1], "atan2")?;
+                    Ok(Value::Number(y.atan2(x)))
+                }),
+            })),
+        );
+
+        // ln(x) - natural logarithm
+        self.globals.insert(
+            String::from("ln"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("ln"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| Ok(Value::Number(positive_arg(vm, &args[0], "ln")?.ln()))),
+            })),
+        );
+
+        // log(x, basis?) - logarithm, base 10 by default
+        self.globals.insert(
+            String::from("log"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("log"),
+                arity: Arity::Range(1, 2),
+                func: Rc::new(|vm, args| {
+                    let x = positive_arg(vm, &args[0], "log")?;
+                    match args.get(1) {
+                        None => Ok(Value::Number(x.log10())),
+                        Some(base) => {
+                            let base = positive_arg(vm, base, "log")?;
+                            if base == 1.0 {
+                                return Err(vm.fail(ErrorKind::Value, "log() kan nie basis 1 gebruik nie.".to_string()));
+                            }
+                            Ok(Value::Number(x.log(base)))
+                        }
+                    }
+                }),
+            })),
+        );
+
+        // min(lys) or min(a, b, ...) - the smallest value
+        // maks(lys) or maks(a, b, ...) - the largest value
+        for (name, wanted) in [("min", Ordering::Less), ("maks", Ordering::Greater)] {
+            self.globals.insert(
+                String::from(name),
+                Value::NativeFunction(Rc::new(NativeFunction {
+                    name: String::from(name),
+                    arity: Arity::AtLeast(1),
+                    func: Rc::new(move |vm, args| {
+                        let items = match args {
+                            [Value::List(items)] => items.as_slice(),
+                            [value] => return Err(vm.fail(ErrorKind::Type, format!("{}() verwag 'n lys of meer as een waarde, nie {} nie.", name, value.type_name()))),
+                            _ => args,
+                        };
+                        let mut best = items
+                            .first()
+                            .ok_or_else(|| vm.fail(ErrorKind::EmptyList, format!("Kan nie {} van leë lys kry nie.", name)))?;
+                        for item in &items[1..] {
+                            if vm.compare_values(item, best, name)? == Some(wanted) {
+                                best = item;
+                            }
+                        }
+                        Ok(best.clone())
+                    }),
+                })),
+            );
+        }
+
+        // saad(n) - seed the random generator so the numbers repeat every run
+        self.globals.insert(
+            String::from("saad"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("saad"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| match &args[0] {
+                    Value::Integer(seed) => {
+                        vm.random = Random::new(*seed as u64);
+                        Ok(Value::Nil)
+                    }
+                    _ => Err(vm.fail(ErrorKind::Type, "saad() verwag 'n heelgetal.".to_string())),
+                }),
+            })),
+        );
+
+        // willekeurig() - a random float in [0, 1)
+        self.globals.insert(
+            String::from("willekeurig"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("willekeurig"),
+                arity: Arity::Exact(0),
+                func: Rc::new(|vm, _| Ok(Value::Number(vm.random.next_f64()))),
+            })),
+        );
+
+        // willekeurig_heel(min, maks) - a random integer from min to maks inclusive
+        self.globals.insert(
+            String::from("willekeurig_heel"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("willekeurig_heel"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| match (&args[0], &args[1]) {
+                    (Value::Integer(low), Value::Integer(high)) if low <= high => {
+                        let span = high.abs_diff(*low);
+                        let offset = match span.checked_add(1) {
+                            Some(count) => vm.random.below(count),
+                            None => vm.random.next_u64(),
+                        };
+                        Ok(Value::Integer(low.wrapping_add_unsigned(offset)))
+                    }
+                    (Value::Integer(low), Value::Integer(high)) => {
+                        Err(vm.fail(ErrorKind::Value, format!("willekeurig_heel() verwag min <= maks, nie {} en {} nie.", low, high)))
+                    }
+                    _ => Err(vm.fail(ErrorKind::Type, "willekeurig_heel() verwag twee heelgetalle.".to_string())),
+                }),
+            })),
+        );
+
+        // skommel(lys) - a copy of the list in random order
+        self.globals.insert(
+            String::from("skommel"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("skommel"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    let mut items = list_arg(vm, &args[0], "skommel")?.as_ref().clone();
+                    for i in (1..items.len()).rev() {
+                        let j = vm.random.below(i as u64 + 1) as usize;
+                        items.swap(i, j);
+                    }
+                    Ok(Value::List(Rc::new(items)))
+                }),
+            })),
+        );
+
+        // rits(lys1, lys2) - pairs up elements into a list of tuples
+        self.globals.insert(
+            String::from("rits"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("rits"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::List(a), Value::List(b)) => {
+                            let pairs: Vec<Value> = a
+                                .iter()
+                                .zip(b.iter())
+                                .map(|(x, y)| Value::Tuple(Rc::new(vec![x.clone(), y.clone()])))
+                                .collect();
+                            Ok(Value::List(Rc::new(pairs)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "rits() verwag twee lyste.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // zip(lys1, lys2) - the same function as rits
+        let rits = self.globals["rits"].clone();
+        self.globals.insert(String::from("zip"), rits);
+
+        // versameling(lys) - builds a set from the elements of a list
+        self.globals.insert(
+            String::from("versameling"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("versameling"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(items) => {
+                            let set = Set::from_values(items.iter().cloned()).map_err(|e| vm.fail(ErrorKind::Type, e))?;
+                            Ok(Value::Versameling(Rc::new(set)))
+                        }
+                        Value::Versameling(_) => Ok(args[0].clone()),
+                        _ => Err(vm.fail(ErrorKind::Type, "versameling() verwag 'n lys.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // vereniging(a, b) - elements in either set
+        self.globals.insert(
+            String::from("vereniging"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("vereniging"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::Versameling(a), Value::Versameling(b)) => {
+                            let mut union = a.as_ref().clone();
+                            for item in b.items() {
+                                union.insert(item.clone()).map_err(|e| vm.fail(ErrorKind::Type, e))?;
+                            }
+                            Ok(Value::Versameling(Rc::new(union)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "vereniging() verwag twee versamelings.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // snyding(a, b) - elements in both sets
+        self.globals.insert(
+            String::from("snyding"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("snyding"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::Versameling(a), Value::Versameling(b)) => {
+                            let mut intersection = Set::new();
+                            for item in a.items() {
+                                if b.contains(item)? {
+                                    intersection.insert(item.clone()).map_err(|e| vm.fail(ErrorKind::Type, e))?;
+                                }
+                            }
+                            Ok(Value::Versameling(Rc::new(intersection)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "snyding() verwag twee versamelings.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // verskil(a, b) - elements in a but not in b
+        self.globals.insert(
+            String::from("verskil"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("verskil"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::Versameling(a), Value::Versameling(b)) => {
+                            let mut difference = Set::new();
+                            for item in a.items() {
+                                if !b.contains(item)? {
+                                    difference.insert(item.clone()).map_err(|e| vm.fail(ErrorKind::Type, e))?;
+                                }
+                            }
+                            Ok(Value::Versameling(Rc::new(difference)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "verskil() verwag twee versamelings.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // is_deel_van(a, b) - true if every element of a is in b
+        self.globals.insert(
+            String::from("is_deel_van"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("is_deel_van"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::Versameling(a), Value::Versameling(b)) => {
+                            for item in a.items() {
+                                if !b.contains(item)? {
+                                    return Ok(Value::Boolean(false));
+                                }
+                            }
+                            Ok(Value::Boolean(true))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "is_deel_van() verwag twee versamelings.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // vergelyk(a, b) - -1, 0 or 1 as a is smaller than, equal to or larger than b
+        self.globals.insert(
+            String::from("vergelyk"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("vergelyk"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let ordering = args[0].compare(&args[1]).map_err(|e| vm.fail(ErrorKind::Type, format!("vergelyk(): {}.", e)))?;
+                    match ordering {
+                        Some(Ordering::Less) => Ok(Value::Integer(-1)),
+                        Some(Ordering::Equal) => Ok(Value::Integer(0)),
+                        Some(Ordering::Greater) => Ok(Value::Integer(1)),
+                        None => Err(vm.fail(ErrorKind::Value, "vergelyk() kan nie NaN vergelyk nie.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // verdeel(s, skeier) - splits a string; an empty separator gives the characters
+        self.globals.insert(
+            String::from("verdeel"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("verdeel"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::String(s), Value::String(sep)) => {
+                            let parts: Vec<Value> = if sep.is_empty() {
+                                s.chars().map(|c| Value::String(Rc::new(c.to_string()))).collect()
+                            } else {
+                                s.split(sep.as_str()).map(|p| Value::String(Rc::new(p.to_string()))).collect()
+                            };
+                            Ok(Value::List(Rc::new(parts)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "verdeel() verwag 'n string en 'n skeier-string.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // voeg_saam(lys, skeier) - joins the elements of a list into one string
+        self.globals.insert(
+            String::from("voeg_saam"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("voeg_saam"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::List(items), Value::String(sep)) => {
+                            let parts: Vec<String> = items.iter().map(|v| v.to_string()).collect();
+                            let separators = sep.len() * parts.len().saturating_sub(1);
+                            vm.check_size(parts.iter().map(String::len).sum::<usize>() + separators)?;
+                            Ok(Value::String(Rc::new(parts.join(sep.as_str()))))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "voeg_saam() verwag 'n lys en 'n skeier-string.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // sny(s, begin, einde) - characters (or list elements) from begin up to
+        // (not including) einde; negative positions count from the end
+        self.globals.insert(
+            String::from("sny"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("sny"),
+                arity: Arity::Exact(3),
+                func: Rc::new(|vm, args| {
+                    let len = match &args[0] {
+                        Value::String(s) => s.chars().count() as i64,
+                        Value::List(items) => items.len() as i64,
+                        _ => return Err(vm.fail(ErrorKind::Type, "sny() verwag 'n string of lys.".to_string())),
+                    };
+                    let mut position = |value: &Value| -> Result<usize, String> {
+                        let n = match value {
+                            Value::Integer(n) => *n,
+                            Value::Number(n) if n.fract() == 0.0 => *n as i64,
+                            _ => return Err(vm.fail(ErrorKind::Type, "sny() verwag heelgetal posisies.".to_string())),
+                        };
+                        let n = if n < 0 { len + n } else { n };
+                        Ok(n.clamp(0, len) as usize)
+                    };
+                    let begin = position(&args[1])?;
+                    let end = position(&args[2])?;
+                    let count = end.saturating_sub(begin);
+                    match &args[0] {
+                        Value::List(items) => Ok(Value::List(Rc::new(items.iter().skip(begin).take(count).cloned().collect()))),
+                        s => Ok(Value::String(Rc::new(s.to_string().chars().skip(begin).take(count).collect()))),
+                    }
+                }),
+            })),
+        );
+
+        // trim(s) - removes whitespace from both ends
+        self.globals.insert(
+            String::from("trim"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("trim"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::String(s) => Ok(Value::String(Rc::new(s.trim().to_string()))),
+                        _ => Err(vm.fail(ErrorKind::Type, "trim() verwag 'n string.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // hoofletters(s) - converts to upper case
+        self.globals.insert(
+            String::from("hoofletters"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("hoofletters"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::String(s) => Ok(Value::String(Rc::new(s.to_uppercase()))),
+                        _ => Err(vm.fail(ErrorKind::Type, "hoofletters() verwag 'n string.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // kleinletters(s) - converts to lower case
+        self.globals.insert(
+            String::from("kleinletters"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("kleinletters"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::String(s) => Ok(Value::String(Rc::new(s.to_lowercase()))),
+                        _ => Err(vm.fail(ErrorKind::Type, "kleinletters() verwag 'n string.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // begin_met(s, voorvoegsel) - does the string start with the prefix?
+        self.globals.insert(
+            String::from("begin_met"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("begin_met"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::String(s), Value::String(prefix)) => Ok(Value::Boolean(s.starts_with(prefix.as_str()))),
+                        _ => Err(vm.fail(ErrorKind::Type, "begin_met() verwag twee strings.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // eindig_met(s, agtervoegsel) - does the string end with the suffix?
+        self.globals.insert(
+            String::from("eindig_met"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("eindig_met"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::String(s), Value::String(suffix)) => Ok(Value::Boolean(s.ends_with(suffix.as_str()))),
+                        _ => Err(vm.fail(ErrorKind::Type, "eindig_met() verwag twee strings.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // vervang(s, oud, nuut) - replaces every occurrence of oud
+        self.globals.insert(
+            String::from("vervang"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("vervang"),
+                arity: Arity::Exact(3),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1], &args[2]) {
+                        (Value::String(_), Value::String(old), Value::String(_)) if old.is_empty() => {
+                            Err(vm.fail(ErrorKind::Value, "vervang() kan nie 'n leë string vervang nie.".to_string()))
+                        }
+                        (Value::String(s), Value::String(old), Value::String(new)) => {
+                            Ok(Value::String(Rc::new(s.replace(old.as_str(), new))))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "vervang() verwag drie strings.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // indeks_van(s, deel) - character position of the first occurrence, or -1
+        self.globals.insert(
+            String::from("indeks_van"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("indeks_van"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::String(s), Value::String(sub)) => Ok(Value::Integer(match s.find(sub.as_str()) {
+                            // Count characters, not bytes, like string indexing does
+                            Some(byte) => s[..byte].chars().count() as i64,
+                            None => -1,
+                        })),
+                        _ => Err(vm.fail(ErrorKind::Type, "indeks_van() verwag twee strings.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // herhaal(s, n) - the string repeated n times
+        self.globals.insert(
+            String::from("herhaal"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("herhaal"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let count = match &args[1] {
+                        Value::Integer(n) if *n >= 0 => *n as usize,
+                        Value::Number(n) if *n >= 0.0 && n.fract() == 0.0 => *n as usize,
+                        _ => return Err(vm.fail(ErrorKind::Type, "herhaal() verwag 'n nie-negatiewe heelgetal.".to_string())),
+                    };
+                    match &args[0] {
+                        Value::String(s) => {
+                            vm.check_size(s.len().saturating_mul(count))?;
+                            Ok(Value::String(Rc::new(s.repeat(count))))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "herhaal() verwag 'n string.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // karakters(s) - list of the characters in a string
+        self.globals.insert(
+            String::from("karakters"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("karakters"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::String(s) => Ok(Value::List(Rc::new(
+                            s.chars().map(|c| Value::String(Rc::new(c.to_string()))).collect(),
+                        ))),
+                        _ => Err(vm.fail(ErrorKind::Type, "karakters() verwag 'n string.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // na_nommer(s) - parses a string as an integer or float
+        self.globals.insert(
+            String::from("na_nommer"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("na_nommer"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| parse_number(&args[0]).map_err(|(kind, message)| vm.fail(kind, message))),
+            })),
+        );
+
+        // na_string(waarde) - the value as it would be printed
+        self.globals.insert(
+            String::from("na_string"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("na_string"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|_, args| Ok(Value::String(Rc::new(args[0].to_string())))),
+            })),
+        );
+
+        // sorteer(lys) - stable sort in ascending order (see vergelyk)
+        self.globals.insert(
+            String::from("sorteer"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("sorteer"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(items) => {
+                            let sorted = merge_sort(items.to_vec(), &mut |a, b| match a.compare(b) {
+                                Ok(Some(ordering)) => Ok(ordering),
+                                Ok(None) => Err(vm.fail(ErrorKind::Value, "sorteer() kan nie NaN sorteer nie.".to_string())),
+                                Err(e) => Err(vm.fail(ErrorKind::Type, format!("sorteer(): {}.", e))),
+                            })?;
+                            Ok(Value::List(Rc::new(sorted)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "sorteer() verwag 'n lys.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // uniek(lys) - the list without repeated elements, keeping the first of each
+        self.globals.insert(
+            String::from("uniek"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("uniek"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(items) => {
+                            let set = Set::from_values(items.iter().cloned()).map_err(|e| vm.fail(ErrorKind::Type, e))?;
+                            Ok(Value::List(Rc::new(set.items().to_vec())))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "uniek() verwag 'n lys.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // neem(lys, n) - the first n elements
+        self.globals.insert(
+            String::from("neem"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("neem"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], count_arg(vm, &args[1], "neem")?) {
+                        (Value::List(items), n) => Ok(Value::List(Rc::new(items.iter().take(n).cloned().collect()))),
+                        _ => Err(vm.fail(ErrorKind::Type, "neem() verwag 'n lys.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // los(lys, n) - everything after the first n elements
+        self.globals.insert(
+            String::from("los"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("los"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], count_arg(vm, &args[1], "los")?) {
+                        (Value::List(items), n) => Ok(Value::List(Rc::new(items.iter().skip(n).cloned().collect()))),
+                        _ => Err(vm.fail(ErrorKind::Type, "los() verwag 'n lys.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // plat(lys) - flattens one level of nested lists
+        self.globals.insert(
+            String::from("plat"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("plat"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(items) => {
+                            let size = items
+                                .iter()
+                                .map(|item| match item {
+                                    Value::List(inner) => inner.len(),
+                                    _ => 1,
+                                })
+                                .sum();
+                            vm.check_size(size)?;
+                            let mut flat = Vec::with_capacity(size);
+                            for item in items.iter() {
+                                match item {
+                                    Value::List(inner) => flat.extend(inner.iter().cloned()),
+                                    other => flat.push(other.clone()),
+                                }
+                            }
+                            Ok(Value::List(Rc::new(flat)))
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "plat() verwag 'n lys.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // reeks([begin,] einde[, stap]) - numbers from begin (default 0) up to
+        // (not including) einde, with step 1 unless given
+        self.globals.insert(
+            String::from("reeks"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("reeks"),
+                arity: Arity::Range(1, 3),
+                func: Rc::new(|vm, args| {
+                    let args = match args {
+                        [end] => vec![Value::Integer(0), end.clone(), Value::Integer(1)],
+                        [begin, end] => vec![begin.clone(), end.clone(), Value::Integer(1)],
+                        _ => args.to_vec(),
+                    };
+                    if let (Value::Integer(begin), Value::Integer(end), Value::Integer(step)) = (&args[0], &args[1], &args[2]) {
+                        let (begin, end, step) = (*begin, *end, *step);
+                        if step == 0 {
+                            return Err(vm.fail(ErrorKind::Value, "reeks() se stap kan nie nul wees nie.".to_string()));
+                        }
+                        // Count the elements up front so the size limit applies before allocating
+                        let (span, stride) = (end as i128 - begin as i128, step as i128);
+                        let count = if span.signum() == stride.signum() {
+                            (span + stride - stride.signum()) / stride
+                        } else {
+                            0
+                        };
+                        let count = usize::try_from(count).unwrap_or(usize::MAX);
+                        vm.check_size(count)?;
+                        let mut items = Vec::with_capacity(count);
+                        let mut n = begin;
+                        while (step > 0 && n < end) || (step < 0 && n > end) {
+                            items.push(Value::Integer(n));
+                            n = match n.checked_add(step) {
+                                Some(next) => next,
+                                None => break,
+                            };
+                        }
+                        return Ok(Value::List(Rc::new(items)));
+                    }
+
+                    let (begin, end, step) = match (args[0].as_f64(), args[1].as_f64(), args[2].as_f64()) {
+                        (Some(b), Some(e), Some(s)) => (b, e, s),
+                        _ => return Err(vm.fail(ErrorKind::Type, "reeks() verwag drie nommers.".to_string())),
+                    };
+                    if step == 0.0 || !step.is_finite() {
+                        return Err(vm.fail(ErrorKind::Value, "reeks() se stap kan nie nul wees nie.".to_string()));
+                    }
+                    // Count the steps up front so rounding errors can't add an extra element
+                    let count = ((end - begin) / step).ceil().max(0.0) as usize;
+                    vm.check_size(count)?;
+                    let items = (0..count).map(|i| Value::Number(begin + i as f64 * step)).collect();
+                    Ok(Value::List(Rc::new(items)))
+                }),
+            })),
+        );
+
+        // Higher-order functions call back into the VM
+
+        // kaart(lys, fn) - map function over list
+        self.globals.insert(
+            String::from("kaart"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("kaart"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(list) => vm.hof_kaart(Rc::clone(list), args[1].clone()),
+                        Value::Versameling(set) => {
+                            let result = vm.hof_kaart(Rc::new(set.items().to_vec()), args[1].clone())?;
+                            vm.set_from_list(result)
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "kaart() verwag 'n lys of versameling as eerste argument.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // filter(lys, fn) - filter list by predicate
+        self.globals.insert(
+            String::from("filter"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("filter"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(list) => vm.hof_filter(Rc::clone(list), args[1].clone()),
+                        Value::Versameling(set) => {
+                            let result = vm.hof_filter(Rc::new(set.items().to_vec()), args[1].clone())?;
+                            vm.set_from_list(result)
+                        }
+                        _ => Err(vm.fail(ErrorKind::Type, "filter() verwag 'n lys of versameling as eerste argument.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // vou(lys, begin, fn) - fold/reduce list
+        self.globals.insert(
+            String::from("vou"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("vou"),
+                arity: Arity::Exact(3),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(list) => vm.hof_vou(Rc::clone(list), args[1].clone(), args[2].clone()),
+                        Value::Versameling(set) => vm.hof_vou(Rc::new(set.items().to_vec()), args[1].clone(), args[2].clone()),
+                        _ => Err(vm.fail(ErrorKind::Type, "vou() verwag 'n lys of versameling as eerste argument.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // vir_elk(lys, fn) - for each element, call function (returns nil)
+        self.globals.insert(
+            String::from("vir_elk"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("vir_elk"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match &args[0] {
+                        Value::List(list) => vm.hof_vir_elk(Rc::clone(list), args[1].clone()),
+                        Value::Versameling(set) => vm.hof_vir_elk(Rc::new(set.items().to_vec()), args[1].clone()),
+                        _ => Err(vm.fail(ErrorKind::Type, "vir_elk() verwag 'n lys of versameling as eerste argument.".to_string())),
+                    }
+                }),
+            })),
+        );
+
+        // sorteer_met(lys, fn(a, b)) - stable sort with a comparison function
+        self.globals.insert(
+            String::from("sorteer_met"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("sorteer_met"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let list = list_arg(vm, &args[0], "sorteer_met")?;
+                    vm.hof_sorteer_met(list, args[1].clone())
+                }),
+            })),
+        );
+
+        // sorteer_volgens(lys, fn) - stable sort by the key fn gives each element
+        self.globals.insert(
+            String::from("sorteer_volgens"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("sorteer_volgens"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let list = list_arg(vm, &args[0], "sorteer_volgens")?;
+                    vm.hof_sorteer_volgens(list, args[1].clone())
+                }),
+            })),
+        );
+
+        // vind(lys, fn) - the first element for which fn is true, or nil
+        self.globals.insert(
+            String::from("vind"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("vind"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let list = list_arg(vm, &args[0], "vind")?;
+                    Ok(vm.hof_soek(&list, args[1].clone())?.map_or(Value::Nil, |i| list[i].clone()))
+                }),
+            })),
+        );
+
+        // indeks(lys, fn) - position of the first element for which fn is true, or -1
+        self.globals.insert(
+            String::from("indeks"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("indeks"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let list = list_arg(vm, &args[0], "indeks")?;
+                    Ok(Value::Integer(vm.hof_soek(&list, args[1].clone())?.map_or(-1, |i| i as i64)))
+                }),
+            })),
+        );
+
+        // enige(lys, fn) - is fn true for any element?
+        self.globals.insert(
+            String::from("enige"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("enige"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let list = list_arg(vm, &args[0], "enige")?;
+                    Ok(Value::Boolean(vm.hof_soek(&list, args[1].clone())?.is_some()))
+                }),
+            })),
+        );
+
+        // almal(lys, fn) - is fn true for every element?
+        self.globals.insert(
+            String::from("almal"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("almal"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let list = list_arg(vm, &args[0], "almal")?;
+                    let func = args[1].clone();
+                    for item in list.iter() {
+                        if !vm.call_value(func.clone(), vec![item.clone()])?.is_truthy() {
+                            return Ok(Value::Boolean(false));
+                        }
+                    }
+                    Ok(Value::Boolean(true))
+                }),
+            })),
+        );
+
+        // groepeer(lys, fn) - dictionary from each key fn gives to the elements with that key
+        self.globals.insert(
+            String::from("groepeer"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("groepeer"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let list = list_arg(vm, &args[0], "groepeer")?;
+                    vm.hof_groepeer(list, args[1].clone())
+                }),
+            })),
+        );
+
+        // Built-in types: Opsie, Resultaat and Uitsondering
+        for (_, constructors) in BUILTIN_TYPES {
+            for (name, _) in constructors.iter() {
+                self.globals.insert(
+                    name.to_string(),
+                    Value::TypeConstructor(Rc::new(builtin_constructor(name))),
+                );
+            }
+        }
+
+        // kaart_opsie(o, fn) - apply fn to the value in a Sommige or Ok
+        self.globals.insert(
+            String::from("kaart_opsie"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("kaart_opsie"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match unwrap_success(&args[0]).map_err(|e| vm.fail(ErrorKind::Type, format!("kaart_opsie() verwag {}.", e)))? {
+                        Some(value) => {
+                            let mapped = vm.call_value(args[1].clone(), vec![value])?;
+                            let constructor = match &args[0] {
+                                Value::Adt(adt) => adt.constructor_name.clone(),
+                                _ => String::from("Sommige"),
+                            };
+                            Ok(construct(&constructor, vec![mapped]))
+                        }
+                        None => Ok(args[0].clone()),
+                    }
+                }),
+            })),
+        );
+
+        // en_dan(o, fn) - fn's result (itself an Opsie or Resultaat) for a
+        // Sommige or Ok; Niks and Fout pass through
+        self.globals.insert(
+            String::from("en_dan"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("en_dan"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match unwrap_success(&args[0]).map_err(|e| vm.fail(ErrorKind::Type, format!("en_dan() verwag {}.", e)))? {
+                        Some(value) => vm.call_value(args[1].clone(), vec![value]),
+                        None => Ok(args[0].clone()),
+                    }
+                }),
+            })),
+        );
+
+        // of_anders(o, standaard) - the value in a Sommige or Ok, else standaard
+        self.globals.insert(
+            String::from("of_anders"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("of_anders"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let value = unwrap_success(&args[0]).map_err(|e| vm.fail(ErrorKind::Type, format!("of_anders() verwag {}.", e)))?;
+                    Ok(value.unwrap_or_else(|| args[1].clone()))
+                }),
+            })),
+        );
+
+        // is_ok(o) - whether o is a Sommige or Ok
+        self.globals.insert(
+            String::from("is_ok"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("is_ok"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    let value = unwrap_success(&args[0]).map_err(|e| vm.fail(ErrorKind::Type, format!("is_ok() verwag {}.", e)))?;
+                    Ok(Value::Boolean(value.is_some()))
+                }),
+            })),
+        );
+
+        // Safe variants: absence gives Niks and failure gives Fout instead of an error
+
+        // kop_veilig(lys) - Sommige(first element), or Niks for an empty list
+        self.globals.insert(
+            String::from("kop_veilig"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("kop_veilig"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    let list = list_arg(vm, &args[0], "kop_veilig")?;
+                    Ok(opsie(list.first().cloned()))
+                }),
+            })),
+        );
+
+        // stert_veilig(lys) - Sommige(all but the first element), or Niks for an empty list
+        self.globals.insert(
+            String::from("stert_veilig"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("stert_veilig"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    let list = list_arg(vm, &args[0], "stert_veilig")?;
+                    Ok(opsie(list.get(1..).map(|rest| Value::List(Rc::new(rest.to_vec())))))
+                }),
             })),
         );
 
-        // kop(lys) - returns the first element of a list
+        // kry_veilig(versameling, sleutel) - Sommige(value) for a dictionary key or
+        // list index, or Niks when it is missing
         self.globals.insert(
-            String::from("kop"),
+            String::from("kry_veilig"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("kop"),
-                arity: 1,
-                func: |args| {
-                    match &args[0] {
-                        Value::List(items) => {
-                            if items.is_empty() {
-                                Err("Kan nie kop van leë lys kry nie.".to_string())
-                            } else {
-                                Ok(items[0].clone())
-                            }
+                name: String::from("kry_veilig"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    match (&args[0], &args[1]) {
+                        (Value::Woordeboek(d), key) => Ok(opsie(d.get(key)?.cloned())),
+                        (Value::List(items), Value::Integer(i)) => {
+                            Ok(opsie(usize::try_from(*i).ok().and_then(|i| items.get(i)).cloned()))
                         }
-                        _ => Err("kop() verwag 'n lys.".to_string()),
+                        _ => Err(vm.fail(ErrorKind::Type, "kry_veilig() verwag 'n woordeboek, of 'n lys en 'n heelgetal.".to_string())),
                     }
-                },
+                }),
             })),
         );
 
-        // stert(lys) - returns all but the first element of a list
+        // vind_veilig(lys, fn) - Sommige(first element for which fn is true), or Niks
         self.globals.insert(
-            String::from("stert"),
+            String::from("vind_veilig"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("stert"),
-                arity: 1,
-                func: |args| {
-                    match &args[0] {
-                        Value::List(items) => {
-                            if items.is_empty() {
-                                Err("Kan nie stert van leë lys kry nie.".to_string())
-                            } else {
-                                let tail: Vec<Value> = items[1..].to_vec();
-                                Ok(Value::List(Rc::new(tail)))
-                            }
-                        }
-                        _ => Err("stert() verwag 'n lys.".to_string()),
-                    }
-                },
+                name: String::from("vind_veilig"),
+                arity: Arity::Exact(2),
+                func: Rc::new(|vm, args| {
+                    let list = list_arg(vm, &args[0], "vind_veilig")?;
+                    Ok(opsie(vm.hof_soek(&list, args[1].clone())?.map(|i| list[i].clone())))
+                }),
             })),
         );
 
-        // leeg(lys) - returns true if list is empty
+        // na_nommer_veilig(s) - Ok(number), or Fout(boodskap) if s isn't a number
         self.globals.insert(
-            String::from("leeg"),
+            String::from("na_nommer_veilig"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("leeg"),
-                arity: 1,
-                func: |args| {
-                    match &args[0] {
-                        Value::List(items) => Ok(Value::Boolean(items.is_empty())),
-                        Value::String(s) => Ok(Value::Boolean(s.is_empty())),
-                        _ => Err("leeg() verwag 'n lys of string.".to_string()),
-                    }
-                },
+                name: String::from("na_nommer_veilig"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|_, args| Ok(resultaat(parse_number(&args[0]).map_err(|(_, message)| message)))),
             })),
         );
 
-        // voeg_by(element, lys) - prepends element to list (cons)
+        // skryf(x) - print without a trailing newline
         self.globals.insert(
-            String::from("voeg_by"),
+            String::from("skryf"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("voeg_by"),
-                arity: 2,
-                func: |args| {
-                    match &args[1] {
-                        Value::List(items) => {
-                            let mut new_list = vec![args[0].clone()];
-                            new_list.extend(items.iter().cloned());
-                            Ok(Value::List(Rc::new(new_list)))
-                        }
-                        _ => Err("voeg_by() verwag 'n lys as tweede argument.".to_string()),
-                    }
-                },
+                name: String::from("skryf"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    write!(vm.output, "{}", args[0]).map_err(|e| vm.io_error(e))?;
+                    vm.output.flush().map_err(|e| vm.io_error(e))?;
+                    Ok(Value::Nil)
+                }),
             })),
         );
 
-        // heg_aan(lys, element) - appends element to list
+        // druk_fout(x) - print a line to the error stream
         self.globals.insert(
-            String::from("heg_aan"),
+            String::from("druk_fout"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("heg_aan"),
-                arity: 2,
-                func: |args| {
-                    match &args[0] {
-                        Value::List(items) => {
-                            let mut new_list = items.as_ref().clone();
-                            new_list.push(args[1].clone());
-                            Ok(Value::List(Rc::new(new_list)))
-                        }
-                        _ => Err("heg_aan() verwag 'n lys as eerste argument.".to_string()),
-                    }
-                },
+                name: String::from("druk_fout"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    vm.output.flush().map_err(|e| vm.io_error(e))?;
+                    writeln!(vm.error_output, "{}", args[0]).map_err(|e| vm.io_error(e))?;
+                    Ok(Value::Nil)
+                }),
             })),
         );
 
-        // ketting(lys1, lys2) - concatenates two lists
+        // lees_lyn() - read one line without its line ending, nil at end of input
         self.globals.insert(
-            String::from("ketting"),
+            String::from("lees_lyn"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("ketting"),
-                arity: 2,
-                func: |args| {
-                    match (&args[0], &args[1]) {
-                        (Value::List(a), Value::List(b)) => {
-                            let mut new_list = a.as_ref().clone();
-                            new_list.extend(b.iter().cloned());
-                            Ok(Value::List(Rc::new(new_list)))
+                name: String::from("lees_lyn"),
+                arity: Arity::Exact(0),
+                func: Rc::new(|vm, _| {
+                    vm.output.flush().map_err(|e| vm.io_error(e))?;
+                    let mut line = String::new();
+                    if vm.input.read_line(&mut line).map_err(|e| vm.io_error(e))? == 0 {
+                        return Ok(Value::Nil);
+                    }
+                    if line.ends_with('\n') {
+                        line.pop();
+                        if line.ends_with('\r') {
+                            line.pop();
                         }
-                        _ => Err("ketting() verwag twee lyste.".to_string()),
                     }
-                },
+                    Ok(Value::String(Rc::new(line)))
+                }),
             })),
         );
 
-        // omgekeer(lys) - reverses a list
+        // lees_alles() - read the rest of the input
         self.globals.insert(
-            String::from("omgekeer"),
+            String::from("lees_alles"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("omgekeer"),
-                arity: 1,
-                func: |args| {
-                    match &args[0] {
-                        Value::List(items) => {
-                            let reversed: Vec<Value> = items.iter().rev().cloned().collect();
-                            Ok(Value::List(Rc::new(reversed)))
-                        }
-                        _ => Err("omgekeer() verwag 'n lys.".to_string()),
-                    }
-                },
+                name: String::from("lees_alles"),
+                arity: Arity::Exact(0),
+                func: Rc::new(|vm, _| {
+                    vm.output.flush().map_err(|e| vm.io_error(e))?;
+                    let limit = vm.limits.max_collection_size;
+                    let bytes = read_bytes(&mut vm.input, limit).map_err(|e| vm.io_error(e))?;
+                    vm.check_size(bytes.len())?;
+                    let text = String::from_utf8(bytes)
+                        .map_err(|e| vm.io_error(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+                    Ok(Value::String(Rc::new(text)))
+                }),
             })),
         );
 
-        // Higher-order functions are handled specially in Call opcode
-        // These are placeholder registrations so they're recognized as functions
+        // argumente() - the script's command-line arguments as a list of strings
+        self.globals.insert(
+            String::from("argumente"),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: String::from("argumente"),
+                arity: Arity::Exact(0),
+                func: Rc::new(|vm, _| {
+                    let arguments = vm.arguments.iter().map(|arg| Value::String(Rc::new(arg.clone()))).collect();
+                    Ok(Value::List(Rc::new(arguments)))
+                }),
+            })),
+        );
 
-        // kaart(lys, fn) - map function over list
+        // omgewing(naam) - Sommige(value) of an environment variable, or Niks if unset
         self.globals.insert(
-            String::from("kaart"),
+            String::from("omgewing"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("kaart"),
-                arity: 2,
-                func: |_| Err("kaart() moet spesiaal hanteer word.".to_string()),
+                name: String::from("omgewing"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    if !vm.limits.allow_environment {
+                        return Err(vm.fail(
+                            ErrorKind::InputOutput,
+                            "Kan nie omgewing() gebruik nie: omgewingsveranderlikes is hier nie toegelaat nie.".to_string(),
+                        ));
+                    }
+                    let name = string_arg(vm, &args[0], "omgewing")?;
+                    Ok(opsie(std::env::var(name.as_str()).ok().map(|value| Value::String(Rc::new(value)))))
+                }),
             })),
         );
 
-        // filter(lys, fn) - filter list by predicate
+        // verlaat(kode?) - stop the program with an exit code (default 0); can't be caught
         self.globals.insert(
-            String::from("filter"),
+            String::from("verlaat"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("filter"),
-                arity: 2,
-                func: |_| Err("filter() moet spesiaal hanteer word.".to_string()),
+                name: String::from("verlaat"),
+                arity: Arity::Range(0, 1),
+                func: Rc::new(|vm, args| {
+                    let code = match args.first() {
+                        None => 0,
+                        Some(Value::Integer(code @ 0..=255)) => *code as i32,
+                        Some(Value::Integer(_)) => {
+                            return Err(vm.fail(ErrorKind::Value, "verlaat() verwag 'n kode tussen 0 en 255.".to_string()))
+                        }
+                        Some(_) => return Err(vm.fail(ErrorKind::Type, "verlaat() verwag 'n heelgetal.".to_string())),
+                    };
+                    vm.output.flush().map_err(|e| vm.io_error(e))?;
+                    vm.error_output.flush().map_err(|e| vm.io_error(e))?;
+                    vm.exit_code = Some(code);
+                    Err(format!("Program verlaat met kode {}.", code))
+                }),
             })),
         );
 
-        // vou(lys, begin, fn) - fold/reduce list
+        // json_lees(s) - parse a JSON document
         self.globals.insert(
-            String::from("vou"),
+            String::from("json_lees"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("vou"),
-                arity: 3,
-                func: |_| Err("vou() moet spesiaal hanteer word.".to_string()),
+                name: String::from("json_lees"),
+                arity: Arity::Exact(1),
+                func: Rc::new(|vm, args| {
+                    let text = string_arg(vm, &args[0], "json_lees")?;
+                    json::parse(&text).map_err(|e| vm.fail(ErrorKind::Value, e))
+                }),
             })),
         );
 
-        // vir_elk(lys, fn) - for each element, call function (returns nil)
+        // json_skryf(waarde, mooi?) - write a value as JSON, indented if mooi is waar
         self.globals.insert(
-            String::from("vir_elk"),
+            String::from("json_skryf"),
             Value::NativeFunction(Rc::new(NativeFunction {
-                name: String::from("vir_elk"),
-                arity: 2,
-                func: |_| Err("vir_elk() moet spesiaal hanteer word.".to_string()),
+                name: String::from("json_skryf"),
+                arity: Arity::Range(1, 2),
+                func: Rc::new(|vm, args| {
+                    let pretty = match args.get(1) {
+                        None => false,
+                        Some(Value::Boolean(b)) => *b,
+                        Some(_) => return Err(vm.fail(ErrorKind::Type, "json_skryf() verwag 'n boolean as tweede argument.".to_string())),
+                    };
+                    let text = json::stringify(&args[0], pretty).map_err(|e| vm.fail(ErrorKind::Type, e))?;
+                    Ok(Value::String(Rc::new(text)))
+                }),
             })),
         );
+
+        self.globals.insert(String::from("lêer"), file_module());
     }
 
-    pub fn run(&mut self) -> Result<(), String> {
+    pub fn run(&mut self) -> Result<(), Error> {
         // Start executing the main chunk directly (not as a function call)
-        self.run_chunk(&self.chunk.clone())
+        self.start_run();
+        let result = self.run_chunk(&self.chunk.clone());
+        self.finish_run(result)?;
+        Ok(())
+    }
+
+    /// Run another top-level chunk against the existing globals and return its result.
+    /// After an error the stack is cleared so the VM can be used again.
+    pub fn execute(&mut self, chunk: &Chunk) -> Result<Value, Error> {
+        self.start_run();
+        let result = self.run_chunk(chunk);
+        self.finish_run(result)
+    }
+
+    /// Call a function value from the host, as its own run
+    pub fn call(&mut self, callee: Value, args: Vec<Value>) -> Result<Value, Error> {
+        self.start_run();
+        let result = self.call_value(callee, args);
+        self.finish_run(result)
+    }
+
+    fn start_run(&mut self) {
+        self.executed = 0;
+        self.until_clock_check = CLOCK_CHECK_INTERVAL;
+        self.thrown = None;
+        self.error_kind = None;
+        self.exit_code = None;
+        self.limit_exceeded = false;
+        self.deadline = self.limits.timeout.map(|timeout| Instant::now() + timeout);
+    }
+
+    fn finish_run(&mut self, result: Result<Value, String>) -> Result<Value, Error> {
+        result.map_err(|message| {
+            self.stack.clear();
+            self.frames.clear();
+            self.open_upvalues.clear();
+            self.run_error(message)
+        })
+    }
+
+    /// Describe the error that ended a run: an exit, a limit, or whatever
+    /// a 'vang' would have caught
+    fn run_error(&mut self, message: String) -> Error {
+        if let Some(code) = self.exit_code {
+            return Error::Exit(code);
+        }
+        if self.limit_exceeded {
+            return Error::Limit(message);
+        }
+        let kind = self.error_kind.take().unwrap_or(ErrorKind::Runtime);
+        let value = match self.thrown.take() {
+            Some(value) => value,
+            None => return Error::Runtime { kind, message, line: self.line },
+        };
+        if let Value::Adt(adt) = &value {
+            if adt.constructor_name == "Uitsondering" && adt.fields.len() == 3 {
+                let line = match adt.fields[2] {
+                    Value::Integer(line) if line >= 0 => line as usize,
+                    _ => self.line,
+                };
+                if let Some(kind) = ErrorKind::from_name(&adt.fields[0].to_string()) {
+                    return Error::Runtime { kind, message, line };
+                }
+                return Error::Thrown { value, message, line };
+            }
+        }
+        Error::Thrown { value, message, line: self.line }
+    }
+
+    pub fn set_arguments(&mut self, arguments: Vec<String>) {
+        self.arguments = arguments;
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Count one instruction against the budget; the clock is only read
+    /// every CLOCK_CHECK_INTERVAL instructions to keep the check cheap
+    fn tick(&mut self) -> Result<(), String> {
+        self.executed += 1;
+        if let Some(max) = self.limits.max_instructions {
+            if self.executed > max {
+                return Err(self.limit_error(format!("meer as {} instruksies uitgevoer.", max)));
+            }
+        }
+        self.until_clock_check -= 1;
+        if self.until_clock_check == 0 {
+            self.until_clock_check = CLOCK_CHECK_INTERVAL;
+            self.check_deadline()?;
+        }
+        Ok(())
+    }
+
+    /// Mark the run as stopped by a sandbox limit, which no handler may
+    /// catch; the message is what the host reports
+    fn limit_error(&mut self, message: String) -> String {
+        self.limit_exceeded = true;
+        format!("{}: {}", LIMIT_ERROR_PREFIX, message)
+    }
+
+    fn check_deadline(&mut self) -> Result<(), String> {
+        if let (Some(deadline), Some(timeout)) = (self.deadline, self.limits.timeout) {
+            if Instant::now() >= deadline {
+                return Err(self.limit_error(format!("tydlimiet van {:?} verstreek.", timeout)));
+            }
+        }
+        Ok(())
     }
 
-    fn run_chunk(&mut self, chunk: &Chunk) -> Result<(), String> {
+    /// Before computing a number of up to `bits` bits: fail if it would have
+    /// more digits than max_collection_size allows. Big-number operations can
+    /// take long without running many instructions, so the clock is checked too.
+    fn check_number_size(&mut self, bits: u64) -> Result<(), String> {
+        if bits <= 64 {
+            return Ok(());
+        }
+        self.check_deadline()?;
+        // log10(2) is about 0.30103
+        let digits = bits * 30103 / 100_000 + 1;
+        match self.limits.max_collection_size {
+            Some(max) if digits > max as u64 => Err(self.limit_error(format!("getal met meer as {} syfers.", max))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if a collection or string of this size is larger than allowed.
+    /// Natives that know their result size call this before building it.
+    fn check_size(&mut self, size: usize) -> Result<(), String> {
+        match self.limits.max_collection_size {
+            Some(max) if size > max => Err(self.limit_error(format!("waarde met meer as {} elemente.", max))),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_value_size(&mut self, value: &Value) -> Result<(), String> {
+        match value {
+            Value::String(s) => self.check_size(s.len()),
+            Value::List(items) | Value::Tuple(items) => self.check_size(items.len()),
+            Value::Woordeboek(dict) => self.check_size(dict.len()),
+            Value::Versameling(set) => self.check_size(set.len()),
+            Value::BigInt(_) | Value::Rational(_) => self.check_number_size(numeric::size_bits(value)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Register a native function supplied by the embedding program. It stays
+    /// visible inside imported modules as well.
+    pub fn define_native(&mut self, native: NativeFunction) {
+        let native = Rc::new(native);
+        self.host_functions.retain(|existing| existing.name != native.name);
+        self.host_functions.push(Rc::clone(&native));
+        self.globals.insert(native.name.clone(), Value::NativeFunction(native));
+    }
+
+    pub fn set_output(&mut self, output: Box<dyn Write>) {
+        self.output = output;
+    }
+
+    pub fn set_error_output(&mut self, error_output: Box<dyn Write>) {
+        self.error_output = error_output;
+    }
+
+    pub fn set_input(&mut self, input: Box<dyn BufRead>) {
+        self.input = input;
+    }
+
+    pub fn get_global(&self, name: &str) -> Option<&Value> {
+        self.globals.get(name)
+    }
+
+    pub fn set_global(&mut self, name: &str, value: Value) {
+        self.globals.insert(name.to_string(), value);
+    }
+
+    fn run_chunk(&mut self, chunk: &Chunk) -> Result<Value, String> {
+        let mut handlers = Vec::new();
         let mut ip = 0;
+        loop {
+            match self.run_chunk_from(chunk, ip, &mut handlers) {
+                Err(error) => ip = self.catch(&mut handlers, error)?.catch_ip,
+                result => return result,
+            }
+        }
+    }
+
+    fn run_chunk_from(&mut self, chunk: &Chunk, start_ip: usize, handlers: &mut Vec<Handler>) -> Result<Value, String> {
+        let mut ip = start_ip;
 
         loop {
             if ip >= chunk.code.len() {
-                return Ok(());
+                return Ok(Value::Nil);
             }
 
+            self.line = chunk.lines[ip];
+            self.tick()?;
             let instruction = &chunk.code[ip];
             ip += 1;
 
@@ -361,13 +2010,13 @@ impl VM {
                         .globals
                         .get(name)
                         .cloned()
-                        .ok_or_else(|| format!("Ongedefinieerde veranderlike: '{}'", name))?;
+                        .ok_or_else(|| self.fail(ErrorKind::Undefined, format!("Ongedefinieerde veranderlike: '{}'", name)))?;
                     self.push(value);
                 }
                 OpCode::SetVar(name) | OpCode::SetGlobal(name) => {
                     let value = self.peek()?.clone();
                     if !self.globals.contains_key(name) {
-                        return Err(format!("Ongedefinieerde veranderlike: '{}'", name));
+                        return Err(self.fail(ErrorKind::Undefined, format!("Ongedefinieerde veranderlike: '{}'", name)));
                     }
                     self.globals.insert(name.clone(), value);
                 }
@@ -478,76 +2127,71 @@ impl VM {
                     let b = self.pop()?;
                     let a = self.pop()?;
                     match (&a, &b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Number(x + y));
-                        }
                         (Value::String(x), Value::String(y)) => {
-                            let result = format!("{}{}", x, y);
-                            self.push(Value::String(Rc::new(result)));
+                            let result = self.concat(x, y)?;
+                            self.push(result);
                         }
                         (Value::String(x), _) => {
-                            let result = format!("{}{}", x, b);
-                            self.push(Value::String(Rc::new(result)));
+                            let result = self.concat(x, &b.to_string())?;
+                            self.push(result);
                         }
                         (_, Value::String(y)) => {
-                            let result = format!("{}{}", a, y);
-                            self.push(Value::String(Rc::new(result)));
+                            let result = self.concat(&a.to_string(), y)?;
+                            self.push(result);
                         }
-                        _ => return Err("Operande moet nommers of stringe wees vir '+'.".to_string()),
+                        _ if a.as_f64().is_some() && b.as_f64().is_some() => {
+                            let result = self.arithmetic(instruction, &a, &b)?;
+                            self.push(result);
+                        }
+                        _ => return Err(self.fail(ErrorKind::Type, "Operande moet nommers of stringe wees vir '+'.".to_string())),
                     }
                 }
                 OpCode::Subtract => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Number(x - y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '-'.".to_string()),
-                    }
+                    let result = self.arithmetic(instruction, &a, &b)?;
+                    self.push(result);
                 }
                 OpCode::Multiply => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Number(x * y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '*'.".to_string()),
-                    }
+                    let result = self.arithmetic(instruction, &a, &b)?;
+                    self.push(result);
                 }
                 OpCode::Divide => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            if y == 0.0 {
-                                return Err("Deling deur nul.".to_string());
-                            }
-                            self.push(Value::Number(x / y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '/'.".to_string()),
-                    }
+                    let result = self.arithmetic(instruction, &a, &b)?;
+                    self.push(result);
+                }
+                OpCode::IntDivide => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    let result = self.arithmetic(instruction, &a, &b)?;
+                    self.push(result);
                 }
                 OpCode::Modulo => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            if y == 0.0 {
-                                return Err("Modulo deur nul.".to_string());
-                            }
-                            self.push(Value::Number(x % y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '%'.".to_string()),
-                    }
+                    let result = self.arithmetic(instruction, &a, &b)?;
+                    self.push(result);
                 }
                 OpCode::Negate => {
                     let value = self.pop()?;
-                    match value {
-                        Value::Number(n) => self.push(Value::Number(-n)),
-                        _ => return Err("Operand moet 'n nommer wees vir negasie.".to_string()),
-                    }
+                    let result = numeric::negate(&value).map_err(|(kind, message)| self.fail(kind, message))?;
+                    self.push(result);
+                }
+                OpCode::BitAnd | OpCode::BitOr | OpCode::BitXor | OpCode::ShiftLeft | OpCode::ShiftRight => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    self.check_number_size(numeric::result_bits(instruction, &a, &b))?;
+                    let result = numeric::bitwise(instruction, &a, &b).map_err(|(kind, message)| self.fail(kind, message))?;
+                    self.push(result);
+                }
+                OpCode::BitNot => {
+                    let value = self.pop()?;
+                    let result = numeric::bit_not(&value).map_err(|(kind, message)| self.fail(kind, message))?;
+                    self.push(result);
                 }
                 OpCode::Equal => {
                     let b = self.pop()?;
@@ -562,42 +2206,26 @@ impl VM {
                 OpCode::Less => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Boolean(x < y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '<'.".to_string()),
-                    }
+                    let ordering = self.compare_values(&a, &b, "<")?;
+                    self.push(Value::Boolean(ordering == Some(Ordering::Less)));
                 }
                 OpCode::LessEqual => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Boolean(x <= y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '<='.".to_string()),
-                    }
+                    let ordering = self.compare_values(&a, &b, "<=")?;
+                    self.push(Value::Boolean(matches!(ordering, Some(Ordering::Less | Ordering::Equal))));
                 }
                 OpCode::Greater => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Boolean(x > y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '>'.".to_string()),
-                    }
+                    let ordering = self.compare_values(&a, &b, ">")?;
+                    self.push(Value::Boolean(ordering == Some(Ordering::Greater)));
                 }
                 OpCode::GreaterEqual => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Boolean(x >= y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '>='.".to_string()),
-                    }
+                    let ordering = self.compare_values(&a, &b, ">=")?;
+                    self.push(Value::Boolean(matches!(ordering, Some(Ordering::Greater | Ordering::Equal))));
                 }
                 OpCode::Not => {
                     let value = self.pop()?;
@@ -608,7 +2236,7 @@ impl VM {
                 }
                 OpCode::Print => {
                     let value = self.pop()?;
-                    println!("{}", value);
+                    writeln!(self.output, "{}", value).map_err(|e| self.io_error(e))?;
                 }
                 OpCode::Jump(target) => {
                     ip = *target;
@@ -626,10 +2254,10 @@ impl VM {
                     match callee {
                         Value::Function(func) => {
                             if *arg_count != func.arity {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Arguments, format!(
                                     "Verwag {} argumente maar het {} ontvang.",
                                     func.arity, arg_count
-                                ));
+                                )));
                             }
 
                             // Get the function's chunk
@@ -658,10 +2286,10 @@ impl VM {
                         }
                         Value::Closure(closure) => {
                             if *arg_count != closure.function.arity {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Arguments, format!(
                                     "Verwag {} argumente maar het {} ontvang.",
                                     closure.function.arity, arg_count
-                                ));
+                                )));
                             }
 
                             // Get the function's chunk
@@ -689,43 +2317,9 @@ impl VM {
                             self.push(result);
                         }
                         Value::NativeFunction(nf) => {
-                            if *arg_count != nf.arity {
-                                return Err(format!(
-                                    "Verwag {} argumente maar het {} ontvang.",
-                                    nf.arity, arg_count
-                                ));
-                            }
-
                             let args: Vec<Value> = self.stack[callee_idx + 1..].to_vec();
 
-                            // Handle higher-order functions specially
-                            let result = match nf.name.as_str() {
-                                "kaart" => {
-                                    match &args[0] {
-                                        Value::List(list) => self.hof_kaart(Rc::clone(list), args[1].clone())?,
-                                        _ => return Err("kaart() verwag 'n lys as eerste argument.".to_string()),
-                                    }
-                                }
-                                "filter" => {
-                                    match &args[0] {
-                                        Value::List(list) => self.hof_filter(Rc::clone(list), args[1].clone())?,
-                                        _ => return Err("filter() verwag 'n lys as eerste argument.".to_string()),
-                                    }
-                                }
-                                "vou" => {
-                                    match &args[0] {
-                                        Value::List(list) => self.hof_vou(Rc::clone(list), args[1].clone(), args[2].clone())?,
-                                        _ => return Err("vou() verwag 'n lys as eerste argument.".to_string()),
-                                    }
-                                }
-                                "vir_elk" => {
-                                    match &args[0] {
-                                        Value::List(list) => self.hof_vir_elk(Rc::clone(list), args[1].clone())?,
-                                        _ => return Err("vir_elk() verwag 'n lys as eerste argument.".to_string()),
-                                    }
-                                }
-                                _ => (nf.func)(&args)?,
-                            };
+                            let result = self.call_native(&nf, args)?;
 
                             self.stack.truncate(callee_idx);
                             self.push(result);
@@ -733,10 +2327,10 @@ impl VM {
                         Value::TypeConstructor(tc) => {
                             // Check arity
                             if *arg_count != tc.arity {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Arguments, format!(
                                     "Konstruktor '{}' verwag {} argumente maar het {} ontvang.",
                                     tc.constructor_name, tc.arity, arg_count
-                                ));
+                                )));
                             }
 
                             // For unit constructors (arity 0), they're already values themselves
@@ -745,14 +2339,18 @@ impl VM {
                                 Value::Adt(Rc::new(AdtInstance {
                                     type_name: tc.type_name.clone(),
                                     constructor_name: tc.constructor_name.clone(),
+                                    tag: tc.tag,
                                     fields: Vec::new(),
+                                    field_names: Rc::clone(&tc.field_names),
                                 }))
                             } else {
                                 let fields: Vec<Value> = self.stack[callee_idx + 1..].to_vec();
                                 Value::Adt(Rc::new(AdtInstance {
                                     type_name: tc.type_name.clone(),
                                     constructor_name: tc.constructor_name.clone(),
+                                    tag: tc.tag,
                                     fields,
+                                    field_names: Rc::clone(&tc.field_names),
                                 }))
                             };
 
@@ -760,54 +2358,70 @@ impl VM {
                             self.push(result);
                         }
                         _ => {
-                            return Err("Kan slegs funksies oproep.".to_string());
+                            return Err(self.fail(ErrorKind::Type, "Kan slegs funksies oproep.".to_string()));
                         }
                     }
                 }
                 OpCode::Return => {
                     // Return from main chunk
-                    return Ok(());
+                    return self.pop();
+                }
+                OpCode::Try(catch_ip) => {
+                    handlers.push(Handler {
+                        catch_ip: *catch_ip,
+                        stack_len: self.stack.len(),
+                        frames_len: self.frames.len(),
+                        function: None,
+                    });
+                }
+                OpCode::EndTry => {
+                    handlers.pop();
+                }
+                OpCode::Throw => {
+                    let value = self.pop()?;
+                    return Err(self.throw(value));
+                }
+                OpCode::CheckFailure => {
+                    let unwrapped = unwrap_success(self.peek()?);
+                    let failed = unwrapped
+                        .map_err(|e| self.fail(ErrorKind::Type, format!("'?' verwag {}.", e)))?
+                        .is_none();
+                    self.push(Value::Boolean(failed));
                 }
                 OpCode::MakeList(count) => {
+                    self.check_size(*count)?;
                     let start = self.stack.len() - *count;
                     let elements: Vec<Value> = self.stack.drain(start..).collect();
                     self.push(Value::List(Rc::new(elements)));
                 }
+                OpCode::Concat(count) => {
+                    let start = self.stack.len() - *count;
+                    let parts: Vec<String> = self.stack.drain(start..).map(|value| value.to_string()).collect();
+                    self.check_size(parts.iter().map(String::len).sum())?;
+                    self.push(Value::String(Rc::new(parts.concat())));
+                }
+                OpCode::MakeTuple(count) => {
+                    self.check_size(*count)?;
+                    let start = self.stack.len() - *count;
+                    let elements: Vec<Value> = self.stack.drain(start..).collect();
+                    self.push(Value::Tuple(Rc::new(elements)));
+                }
+                OpCode::MakeDict(count) => {
+                    self.check_size(*count)?;
+                    let start = self.stack.len() - *count * 2;
+                    let mut dict = Dictionary::new();
+                    let mut pairs = self.stack.drain(start..);
+                    while let (Some(key), Some(value)) = (pairs.next(), pairs.next()) {
+                        dict.insert(key, value)?;
+                    }
+                    drop(pairs);
+                    self.push(Value::Woordeboek(Rc::new(dict)));
+                }
                 OpCode::GetIndex => {
                     let index = self.pop()?;
-                    let list = self.pop()?;
-
-                    match (list, index) {
-                        (Value::List(items), Value::Number(n)) => {
-                            let idx = n as i64;
-                            let len = items.len() as i64;
-                            // Support negative indexing
-                            let actual_idx = if idx < 0 { len + idx } else { idx };
-                            if actual_idx < 0 || actual_idx >= len {
-                                return Err(format!(
-                                    "Lys indeks buite perke: {} (lengte {})",
-                                    idx, len
-                                ));
-                            }
-                            self.push(items[actual_idx as usize].clone());
-                        }
-                        (Value::String(s), Value::Number(n)) => {
-                            let idx = n as i64;
-                            let len = s.chars().count() as i64;
-                            let actual_idx = if idx < 0 { len + idx } else { idx };
-                            if actual_idx < 0 || actual_idx >= len {
-                                return Err(format!(
-                                    "String indeks buite perke: {} (lengte {})",
-                                    idx, len
-                                ));
-                            }
-                            let ch: String = s.chars().nth(actual_idx as usize).unwrap().to_string();
-                            self.push(Value::String(Rc::new(ch)));
-                        }
-                        _ => {
-                            return Err("Kan slegs lyste en stringe indekseer.".to_string());
-                        }
-                    }
+                    let object = self.pop()?;
+                    let value = self.get_index(object, index)?;
+                    self.push(value);
                 }
                 OpCode::CheckConstructor(name, arity) => {
                     let value = self.peek()?;
@@ -831,14 +2445,14 @@ impl VM {
                                 let field_value = adt.fields[*index].clone();
                                 self.push(field_value);
                             } else {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Index, format!(
                                     "Veld indeks {} buite perke vir konstruktor '{}' met {} velde.",
                                     index, adt.constructor_name, adt.fields.len()
-                                ));
+                                )));
                             }
                         }
                         _ => {
-                            return Err("Kan slegs velde van ADT-waardes kry.".to_string());
+                            return Err(self.fail(ErrorKind::Type, "Kan slegs velde van ADT-waardes kry.".to_string()));
                         }
                     }
                 }
@@ -846,21 +2460,47 @@ impl VM {
                     let value = self.peek()?.clone();
                     self.push(value);
                 }
+                OpCode::CheckRange(start, end) => {
+                    let value = self.peek()?;
+                    let matches = matches!(numeric::compare(value, start), Some(Some(Ordering::Greater | Ordering::Equal)))
+                        && matches!(numeric::compare(value, end), Some(Some(Ordering::Less | Ordering::Equal)));
+                    self.push(Value::Boolean(matches));
+                }
+                OpCode::CheckList(len) => {
+                    let matches = match self.peek()? {
+                        Value::List(items) => items.len() == *len,
+                        _ => false,
+                    };
+                    self.push(Value::Boolean(matches));
+                }
+                OpCode::CheckTuple(len) => {
+                    let matches = match self.peek()? {
+                        Value::Tuple(items) => items.len() == *len,
+                        _ => false,
+                    };
+                    self.push(Value::Boolean(matches));
+                }
+                OpCode::MatchFailed(message) => {
+                    return Err(self.fail(ErrorKind::Match, message.clone()));
+                }
                 OpCode::GetFieldPop(index) => {
                     let value = self.pop()?;
                     match value {
+                        Value::List(items) | Value::Tuple(items) if *index < items.len() => {
+                            self.push(items[*index].clone());
+                        }
                         Value::Adt(adt) => {
                             if *index < adt.fields.len() {
                                 self.push(adt.fields[*index].clone());
                             } else {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Index, format!(
                                     "Veld indeks {} buite perke vir konstruktor '{}' met {} velde.",
                                     index, adt.constructor_name, adt.fields.len()
-                                ));
+                                )));
                             }
                         }
                         _ => {
-                            return Err("Kan slegs velde van ADT-waardes kry.".to_string());
+                            return Err(self.fail(ErrorKind::Type, "Kan slegs velde van ADT-waardes kry.".to_string()));
                         }
                     }
                 }
@@ -878,34 +2518,82 @@ impl VM {
                         Value::Module(module) => {
                             if let Some(value) = module.exports.get(member) {
                                 self.push(value.clone());
+                            } else {
+                                return Err(self.fail(
+                                    ErrorKind::Module,
+                                    format!("Module '{}' het nie lid '{}' nie.", module.name, member),
+                                ));
+                            }
+                        }
+                        Value::Adt(adt) => {
+                            if let Some(index) = adt.field_index(member) {
+                                self.push(adt.fields[index].clone());
                             } else {
                                 return Err(format!(
-                                    "Module '{}' het nie lid '{}' nie.",
-                                    module.name, member
+                                    "Konstruktor '{}' het geen veld '{}' nie.",
+                                    adt.constructor_name, member
                                 ));
                             }
                         }
                         _ => {
-                            return Err("Kan slegs lede van modules kry.".to_string());
+                            return Err(self.fail(ErrorKind::Type, "Kan slegs lede van modules of velde van ADT-waardes kry.".to_string()));
                         }
                     }
                 }
+                OpCode::CallNamed(names) => {
+                    self.call_named(names)?;
+                }
+                OpCode::UpdateFields(names) => {
+                    self.update_fields(names)?;
+                }
             }
         }
     }
 
     fn run_function(&mut self, chunk: Rc<Chunk>, slots_start: usize, closure: Option<Rc<Closure>>) -> Result<Value, String> {
+        if let Some(max) = self.limits.max_call_depth {
+            if self.frames.len() > max {
+                return Err(self.limit_error(format!("oproepe dieper as {} vlakke.", max)));
+            }
+        }
+
+        let mut handlers = Vec::new();
+        let (mut chunk, mut slots_start, mut closure, mut ip) = (chunk, slots_start, closure, 0);
+        loop {
+            match self.run_function_from(chunk, slots_start, closure, ip, &mut handlers) {
+                Err(error) => {
+                    let handler = self.catch(&mut handlers, error)?;
+                    ip = handler.catch_ip;
+                    (chunk, slots_start, closure) = handler
+                        .function
+                        .ok_or_else(|| "Hanteerder sonder funksie.".to_string())?;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn run_function_from(
+        &mut self,
+        chunk: Rc<Chunk>,
+        slots_start: usize,
+        closure: Option<Rc<Closure>>,
+        start_ip: usize,
+        handlers: &mut Vec<Handler>,
+    ) -> Result<Value, String> {
         // Use mutable variables to support tail call optimization
         let mut current_chunk = chunk;
         let mut current_slots_start = slots_start;
         let mut current_closure = closure;
-        let mut ip = 0;
+        let mut ip = start_ip;
 
         loop {
             if ip >= current_chunk.code.len() {
                 return Ok(Value::Nil);
             }
 
+            self.line = current_chunk.lines[ip];
+            self.tick()?;
             let instruction = &current_chunk.code[ip];
             ip += 1;
 
@@ -922,13 +2610,13 @@ impl VM {
                         .globals
                         .get(name)
                         .cloned()
-                        .ok_or_else(|| format!("Ongedefinieerde veranderlike: '{}'", name))?;
+                        .ok_or_else(|| self.fail(ErrorKind::Undefined, format!("Ongedefinieerde veranderlike: '{}'", name)))?;
                     self.push(value);
                 }
                 OpCode::SetVar(name) | OpCode::SetGlobal(name) => {
                     let value = self.peek()?.clone();
                     if !self.globals.contains_key(name) {
-                        return Err(format!("Ongedefinieerde veranderlike: '{}'", name));
+                        return Err(self.fail(ErrorKind::Undefined, format!("Ongedefinieerde veranderlike: '{}'", name)));
                     }
                     self.globals.insert(name.clone(), value);
                 }
@@ -1011,76 +2699,71 @@ impl VM {
                     let b = self.pop()?;
                     let a = self.pop()?;
                     match (&a, &b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Number(x + y));
-                        }
                         (Value::String(x), Value::String(y)) => {
-                            let result = format!("{}{}", x, y);
-                            self.push(Value::String(Rc::new(result)));
+                            let result = self.concat(x, y)?;
+                            self.push(result);
                         }
                         (Value::String(x), _) => {
-                            let result = format!("{}{}", x, b);
-                            self.push(Value::String(Rc::new(result)));
+                            let result = self.concat(x, &b.to_string())?;
+                            self.push(result);
                         }
                         (_, Value::String(y)) => {
-                            let result = format!("{}{}", a, y);
-                            self.push(Value::String(Rc::new(result)));
+                            let result = self.concat(&a.to_string(), y)?;
+                            self.push(result);
+                        }
+                        _ if a.as_f64().is_some() && b.as_f64().is_some() => {
+                            let result = self.arithmetic(instruction, &a, &b)?;
+                            self.push(result);
                         }
-                        _ => return Err("Operande moet nommers of stringe wees vir '+'.".to_string()),
+                        _ => return Err(self.fail(ErrorKind::Type, "Operande moet nommers of stringe wees vir '+'.".to_string())),
                     }
                 }
                 OpCode::Subtract => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Number(x - y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '-'.".to_string()),
-                    }
+                    let result = self.arithmetic(instruction, &a, &b)?;
+                    self.push(result);
                 }
                 OpCode::Multiply => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Number(x * y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '*'.".to_string()),
-                    }
+                    let result = self.arithmetic(instruction, &a, &b)?;
+                    self.push(result);
                 }
                 OpCode::Divide => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            if y == 0.0 {
-                                return Err("Deling deur nul.".to_string());
-                            }
-                            self.push(Value::Number(x / y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '/'.".to_string()),
-                    }
+                    let result = self.arithmetic(instruction, &a, &b)?;
+                    self.push(result);
+                }
+                OpCode::IntDivide => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    let result = self.arithmetic(instruction, &a, &b)?;
+                    self.push(result);
                 }
                 OpCode::Modulo => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            if y == 0.0 {
-                                return Err("Modulo deur nul.".to_string());
-                            }
-                            self.push(Value::Number(x % y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '%'.".to_string()),
-                    }
+                    let result = self.arithmetic(instruction, &a, &b)?;
+                    self.push(result);
                 }
                 OpCode::Negate => {
                     let value = self.pop()?;
-                    match value {
-                        Value::Number(n) => self.push(Value::Number(-n)),
-                        _ => return Err("Operand moet 'n nommer wees vir negasie.".to_string()),
-                    }
+                    let result = numeric::negate(&value).map_err(|(kind, message)| self.fail(kind, message))?;
+                    self.push(result);
+                }
+                OpCode::BitAnd | OpCode::BitOr | OpCode::BitXor | OpCode::ShiftLeft | OpCode::ShiftRight => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    self.check_number_size(numeric::result_bits(instruction, &a, &b))?;
+                    let result = numeric::bitwise(instruction, &a, &b).map_err(|(kind, message)| self.fail(kind, message))?;
+                    self.push(result);
+                }
+                OpCode::BitNot => {
+                    let value = self.pop()?;
+                    let result = numeric::bit_not(&value).map_err(|(kind, message)| self.fail(kind, message))?;
+                    self.push(result);
                 }
                 OpCode::Equal => {
                     let b = self.pop()?;
@@ -1095,42 +2778,26 @@ impl VM {
                 OpCode::Less => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Boolean(x < y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '<'.".to_string()),
-                    }
+                    let ordering = self.compare_values(&a, &b, "<")?;
+                    self.push(Value::Boolean(ordering == Some(Ordering::Less)));
                 }
                 OpCode::LessEqual => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Boolean(x <= y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '<='.".to_string()),
-                    }
+                    let ordering = self.compare_values(&a, &b, "<=")?;
+                    self.push(Value::Boolean(matches!(ordering, Some(Ordering::Less | Ordering::Equal))));
                 }
                 OpCode::Greater => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Boolean(x > y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '>'.".to_string()),
-                    }
+                    let ordering = self.compare_values(&a, &b, ">")?;
+                    self.push(Value::Boolean(ordering == Some(Ordering::Greater)));
                 }
                 OpCode::GreaterEqual => {
                     let b = self.pop()?;
                     let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            self.push(Value::Boolean(x >= y));
-                        }
-                        _ => return Err("Operande moet nommers wees vir '>='.".to_string()),
-                    }
+                    let ordering = self.compare_values(&a, &b, ">=")?;
+                    self.push(Value::Boolean(matches!(ordering, Some(Ordering::Greater | Ordering::Equal))));
                 }
                 OpCode::Not => {
                     let value = self.pop()?;
@@ -1141,7 +2808,7 @@ impl VM {
                 }
                 OpCode::Print => {
                     let value = self.pop()?;
-                    println!("{}", value);
+                    writeln!(self.output, "{}", value).map_err(|e| self.io_error(e))?;
                 }
                 OpCode::Jump(target) => {
                     ip = *target;
@@ -1159,10 +2826,10 @@ impl VM {
                     match callee {
                         Value::Function(func) => {
                             if *arg_count != func.arity {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Arguments, format!(
                                     "Verwag {} argumente maar het {} ontvang.",
                                     func.arity, arg_count
-                                ));
+                                )));
                             }
 
                             let func_chunk = Rc::clone(&func.chunk);
@@ -1184,10 +2851,10 @@ impl VM {
                         }
                         Value::Closure(cl) => {
                             if *arg_count != cl.function.arity {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Arguments, format!(
                                     "Verwag {} argumente maar het {} ontvang.",
                                     cl.function.arity, arg_count
-                                ));
+                                )));
                             }
 
                             let func_chunk = Rc::clone(&cl.function.chunk);
@@ -1208,43 +2875,9 @@ impl VM {
                             self.push(result);
                         }
                         Value::NativeFunction(nf) => {
-                            if *arg_count != nf.arity {
-                                return Err(format!(
-                                    "Verwag {} argumente maar het {} ontvang.",
-                                    nf.arity, arg_count
-                                ));
-                            }
-
                             let args: Vec<Value> = self.stack[callee_idx + 1..].to_vec();
 
-                            // Handle higher-order functions specially
-                            let result = match nf.name.as_str() {
-                                "kaart" => {
-                                    match &args[0] {
-                                        Value::List(list) => self.hof_kaart(Rc::clone(list), args[1].clone())?,
-                                        _ => return Err("kaart() verwag 'n lys as eerste argument.".to_string()),
-                                    }
-                                }
-                                "filter" => {
-                                    match &args[0] {
-                                        Value::List(list) => self.hof_filter(Rc::clone(list), args[1].clone())?,
-                                        _ => return Err("filter() verwag 'n lys as eerste argument.".to_string()),
-                                    }
-                                }
-                                "vou" => {
-                                    match &args[0] {
-                                        Value::List(list) => self.hof_vou(Rc::clone(list), args[1].clone(), args[2].clone())?,
-                                        _ => return Err("vou() verwag 'n lys as eerste argument.".to_string()),
-                                    }
-                                }
-                                "vir_elk" => {
-                                    match &args[0] {
-                                        Value::List(list) => self.hof_vir_elk(Rc::clone(list), args[1].clone())?,
-                                        _ => return Err("vir_elk() verwag 'n lys as eerste argument.".to_string()),
-                                    }
-                                }
-                                _ => (nf.func)(&args)?,
-                            };
+                            let result = self.call_native(&nf, args)?;
 
                             self.stack.truncate(callee_idx);
                             self.push(result);
@@ -1252,10 +2885,10 @@ impl VM {
                         Value::TypeConstructor(tc) => {
                             // Check arity
                             if *arg_count != tc.arity {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Arguments, format!(
                                     "Konstruktor '{}' verwag {} argumente maar het {} ontvang.",
                                     tc.constructor_name, tc.arity, arg_count
-                                ));
+                                )));
                             }
 
                             // For unit constructors (arity 0), they're already values themselves
@@ -1264,14 +2897,18 @@ impl VM {
                                 Value::Adt(Rc::new(AdtInstance {
                                     type_name: tc.type_name.clone(),
                                     constructor_name: tc.constructor_name.clone(),
+                                    tag: tc.tag,
                                     fields: Vec::new(),
+                                    field_names: Rc::clone(&tc.field_names),
                                 }))
                             } else {
                                 let fields: Vec<Value> = self.stack[callee_idx + 1..].to_vec();
                                 Value::Adt(Rc::new(AdtInstance {
                                     type_name: tc.type_name.clone(),
                                     constructor_name: tc.constructor_name.clone(),
+                                    tag: tc.tag,
                                     fields,
+                                    field_names: Rc::clone(&tc.field_names),
                                 }))
                             };
 
@@ -1279,10 +2916,32 @@ impl VM {
                             self.push(result);
                         }
                         _ => {
-                            return Err("Kan slegs funksies oproep.".to_string());
+                            return Err(self.fail(ErrorKind::Type, "Kan slegs funksies oproep.".to_string()));
                         }
                     }
                 }
+                OpCode::Try(catch_ip) => {
+                    handlers.push(Handler {
+                        catch_ip: *catch_ip,
+                        stack_len: self.stack.len(),
+                        frames_len: self.frames.len(),
+                        function: Some((Rc::clone(&current_chunk), current_slots_start, current_closure.clone())),
+                    });
+                }
+                OpCode::EndTry => {
+                    handlers.pop();
+                }
+                OpCode::Throw => {
+                    let value = self.pop()?;
+                    return Err(self.throw(value));
+                }
+                OpCode::CheckFailure => {
+                    let unwrapped = unwrap_success(self.peek()?);
+                    let failed = unwrapped
+                        .map_err(|e| self.fail(ErrorKind::Type, format!("'?' verwag {}.", e)))?
+                        .is_none();
+                    self.push(Value::Boolean(failed));
+                }
                 OpCode::Return => {
                     // Get return value
                     let result = self.pop()?;
@@ -1303,10 +2962,10 @@ impl VM {
                     match callee {
                         Value::Function(func) => {
                             if *arg_count != func.arity {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Arguments, format!(
                                     "Verwag {} argumente maar het {} ontvang.",
                                     func.arity, arg_count
-                                ));
+                                )));
                             }
 
                             // Close upvalues for current locals
@@ -1329,10 +2988,10 @@ impl VM {
                         }
                         Value::Closure(cl) => {
                             if *arg_count != cl.function.arity {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Arguments, format!(
                                     "Verwag {} argumente maar het {} ontvang.",
                                     cl.function.arity, arg_count
-                                ));
+                                )));
                             }
 
                             // Close upvalues for current locals
@@ -1354,15 +3013,8 @@ impl VM {
                         Value::NativeFunction(nf) => {
                             // Native functions can't be tail-called in the same way,
                             // just call them and return the result
-                            if *arg_count != nf.arity {
-                                return Err(format!(
-                                    "Verwag {} argumente maar het {} ontvang.",
-                                    nf.arity, arg_count
-                                ));
-                            }
-
                             let args: Vec<Value> = self.stack[callee_idx + 1..].to_vec();
-                            let result = (nf.func)(&args)?;
+                            let result = self.call_native(&nf, args)?;
 
                             self.close_upvalues(current_slots_start);
                             self.stack.truncate(current_slots_start);
@@ -1371,24 +3023,28 @@ impl VM {
                         Value::TypeConstructor(tc) => {
                             // Type constructors just create a value and return it
                             if *arg_count != tc.arity {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Arguments, format!(
                                     "Konstruktor '{}' verwag {} argumente maar het {} ontvang.",
                                     tc.constructor_name, tc.arity, arg_count
-                                ));
+                                )));
                             }
 
                             let result = if tc.arity == 0 {
                                 Value::Adt(Rc::new(AdtInstance {
                                     type_name: tc.type_name.clone(),
                                     constructor_name: tc.constructor_name.clone(),
+                                    tag: tc.tag,
                                     fields: Vec::new(),
+                                    field_names: Rc::clone(&tc.field_names),
                                 }))
                             } else {
                                 let fields: Vec<Value> = self.stack[callee_idx + 1..].to_vec();
                                 Value::Adt(Rc::new(AdtInstance {
                                     type_name: tc.type_name.clone(),
                                     constructor_name: tc.constructor_name.clone(),
+                                    tag: tc.tag,
                                     fields,
+                                    field_names: Rc::clone(&tc.field_names),
                                 }))
                             };
 
@@ -1397,49 +3053,44 @@ impl VM {
                             return Ok(result);
                         }
                         _ => {
-                            return Err("Kan slegs funksies oproep.".to_string());
+                            return Err(self.fail(ErrorKind::Type, "Kan slegs funksies oproep.".to_string()));
                         }
                     }
                 }
                 OpCode::MakeList(count) => {
+                    self.check_size(*count)?;
                     let start = self.stack.len() - *count;
                     let elements: Vec<Value> = self.stack.drain(start..).collect();
                     self.push(Value::List(Rc::new(elements)));
                 }
+                OpCode::Concat(count) => {
+                    let start = self.stack.len() - *count;
+                    let parts: Vec<String> = self.stack.drain(start..).map(|value| value.to_string()).collect();
+                    self.check_size(parts.iter().map(String::len).sum())?;
+                    self.push(Value::String(Rc::new(parts.concat())));
+                }
+                OpCode::MakeTuple(count) => {
+                    self.check_size(*count)?;
+                    let start = self.stack.len() - *count;
+                    let elements: Vec<Value> = self.stack.drain(start..).collect();
+                    self.push(Value::Tuple(Rc::new(elements)));
+                }
+                OpCode::MakeDict(count) => {
+                    self.check_size(*count)?;
+                    let start = self.stack.len() - *count * 2;
+                    let mut dict = Dictionary::new();
+                    let mut pairs = self.stack.drain(start..);
+                    while let (Some(key), Some(value)) = (pairs.next(), pairs.next()) {
+                        dict.insert(key, value)?;
+                    }
+                    drop(pairs);
+                    self.push(Value::Woordeboek(Rc::new(dict)));
+                }
                 OpCode::GetIndex => {
                     let index = self.pop()?;
-                    let list = self.pop()?;
-
-                    match (list, index) {
-                        (Value::List(items), Value::Number(n)) => {
-                            let idx = n as i64;
-                            let len = items.len() as i64;
-                            let actual_idx = if idx < 0 { len + idx } else { idx };
-                            if actual_idx < 0 || actual_idx >= len {
-                                return Err(format!(
-                                    "Lys indeks buite perke: {} (lengte {})",
-                                    idx, len
-                                ));
-                            }
-                            self.push(items[actual_idx as usize].clone());
-                        }
-                        (Value::String(s), Value::Number(n)) => {
-                            let idx = n as i64;
-                            let len = s.chars().count() as i64;
-                            let actual_idx = if idx < 0 { len + idx } else { idx };
-                            if actual_idx < 0 || actual_idx >= len {
-                                return Err(format!(
-                                    "String indeks buite perke: {} (lengte {})",
-                                    idx, len
-                                ));
-                            }
-                            let ch: String = s.chars().nth(actual_idx as usize).unwrap().to_string();
-                            self.push(Value::String(Rc::new(ch)));
-                        }
-                        _ => {
-                            return Err("Kan slegs lyste en stringe indekseer.".to_string());
-                        }
-                    }
+                    let object = self.pop()?;
+                    let value = self.get_index(object, index)?;
+                    self.push(value);
                 }
                 OpCode::CheckConstructor(name, arity) => {
                     let value = self.peek()?;
@@ -1462,14 +3113,14 @@ impl VM {
                                 let field_value = adt.fields[*index].clone();
                                 self.push(field_value);
                             } else {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Index, format!(
                                     "Veld indeks {} buite perke vir konstruktor '{}' met {} velde.",
                                     index, adt.constructor_name, adt.fields.len()
-                                ));
+                                )));
                             }
                         }
                         _ => {
-                            return Err("Kan slegs velde van ADT-waardes kry.".to_string());
+                            return Err(self.fail(ErrorKind::Type, "Kan slegs velde van ADT-waardes kry.".to_string()));
                         }
                     }
                 }
@@ -1477,21 +3128,47 @@ impl VM {
                     let value = self.peek()?.clone();
                     self.push(value);
                 }
+                OpCode::CheckRange(start, end) => {
+                    let value = self.peek()?;
+                    let matches = matches!(numeric::compare(value, start), Some(Some(Ordering::Greater | Ordering::Equal)))
+                        && matches!(numeric::compare(value, end), Some(Some(Ordering::Less | Ordering::Equal)));
+                    self.push(Value::Boolean(matches));
+                }
+                OpCode::CheckList(len) => {
+                    let matches = match self.peek()? {
+                        Value::List(items) => items.len() == *len,
+                        _ => false,
+                    };
+                    self.push(Value::Boolean(matches));
+                }
+                OpCode::CheckTuple(len) => {
+                    let matches = match self.peek()? {
+                        Value::Tuple(items) => items.len() == *len,
+                        _ => false,
+                    };
+                    self.push(Value::Boolean(matches));
+                }
+                OpCode::MatchFailed(message) => {
+                    return Err(self.fail(ErrorKind::Match, message.clone()));
+                }
                 OpCode::GetFieldPop(index) => {
                     let value = self.pop()?;
                     match value {
+                        Value::List(items) | Value::Tuple(items) if *index < items.len() => {
+                            self.push(items[*index].clone());
+                        }
                         Value::Adt(adt) => {
                             if *index < adt.fields.len() {
                                 self.push(adt.fields[*index].clone());
                             } else {
-                                return Err(format!(
+                                return Err(self.fail(ErrorKind::Index, format!(
                                     "Veld indeks {} buite perke vir konstruktor '{}' met {} velde.",
                                     index, adt.constructor_name, adt.fields.len()
-                                ));
+                                )));
                             }
                         }
                         _ => {
-                            return Err("Kan slegs velde van ADT-waardes kry.".to_string());
+                            return Err(self.fail(ErrorKind::Type, "Kan slegs velde van ADT-waardes kry.".to_string()));
                         }
                     }
                 }
@@ -1505,18 +3182,34 @@ impl VM {
                         Value::Module(module) => {
                             if let Some(value) = module.exports.get(member) {
                                 self.push(value.clone());
+                            } else {
+                                return Err(self.fail(
+                                    ErrorKind::Module,
+                                    format!("Module '{}' het nie lid '{}' nie.", module.name, member),
+                                ));
+                            }
+                        }
+                        Value::Adt(adt) => {
+                            if let Some(index) = adt.field_index(member) {
+                                self.push(adt.fields[index].clone());
                             } else {
                                 return Err(format!(
-                                    "Module '{}' het nie lid '{}' nie.",
-                                    module.name, member
+                                    "Konstruktor '{}' het geen veld '{}' nie.",
+                                    adt.constructor_name, member
                                 ));
                             }
                         }
                         _ => {
-                            return Err("Kan slegs lede van modules kry.".to_string());
+                            return Err(self.fail(ErrorKind::Type, "Kan slegs lede van modules of velde van ADT-waardes kry.".to_string()));
                         }
                     }
                 }
+                OpCode::CallNamed(names) => {
+                    self.call_named(names)?;
+                }
+                OpCode::UpdateFields(names) => {
+                    self.update_fields(names)?;
+                }
             }
         }
     }
@@ -1584,7 +3277,7 @@ impl VM {
 
     fn values_equal(&self, a: &Value, b: &Value) -> bool {
         match (a, b) {
-            (Value::Number(x), Value::Number(y)) => x == y,
+            _ if a.as_f64().is_some() && b.as_f64().is_some() => numeric::numbers_equal(a, b),
             (Value::Boolean(x), Value::Boolean(y)) => x == y,
             (Value::String(x), Value::String(y)) => x == y,
             (Value::Nil, Value::Nil) => true,
@@ -1592,27 +3285,211 @@ impl VM {
             (Value::Function(x), Value::Function(y)) => Rc::ptr_eq(x, y),
             (Value::Closure(x), Value::Closure(y)) => Rc::ptr_eq(x, y),
             (Value::NativeFunction(x), Value::NativeFunction(y)) => Rc::ptr_eq(x, y),
-            (Value::TypeConstructor(x), Value::TypeConstructor(y)) => Rc::ptr_eq(x, y),
+            (Value::TypeConstructor(x), Value::TypeConstructor(y)) => {
+                x.type_name == y.type_name && x.constructor_name == y.constructor_name
+            }
             (Value::Adt(x), Value::Adt(y)) => {
                 x.type_name == y.type_name
                     && x.constructor_name == y.constructor_name
                     && x.fields.len() == y.fields.len()
                     && x.fields.iter().zip(y.fields.iter()).all(|(a, b)| self.values_equal(a, b))
             }
+            // A bare unit constructor and its zero-field instance
+            (Value::TypeConstructor(_), Value::Adt(_)) | (Value::Adt(_), Value::TypeConstructor(_)) => a == b,
             (Value::Module(x), Value::Module(y)) => Rc::ptr_eq(x, y),
+            (Value::Woordeboek(x), Value::Woordeboek(y)) => x == y,
+            (Value::Versameling(x), Value::Versameling(y)) => x == y,
+            (Value::Tuple(x), Value::Tuple(y)) => x == y,
             _ => false,
         }
     }
 
+    /// Arithmetic on two numbers (see numeric::arithmetic)
+    fn arithmetic(&mut self, op: &OpCode, a: &Value, b: &Value) -> Result<Value, String> {
+        self.check_number_size(numeric::result_bits(op, a, b))?;
+        numeric::arithmetic(op, a, b, self.exact_division).map_err(|(kind, message)| self.fail(kind, message))
+    }
+
+    /// `object[index]`. Lists and strings take integer indexes (floats are
+    /// truncated) counting from the end when negative; dictionaries take any key.
+    fn get_index(&mut self, object: Value, index: Value) -> Result<Value, String> {
+        let position = match index {
+            Value::Integer(i) => Some(i),
+            Value::Number(n) => Some(n as i64),
+            _ => None,
+        };
+        match (object, position) {
+            (Value::List(items), Some(idx)) => {
+                let len = items.len() as i64;
+                // Support negative indexing
+                let actual_idx = if idx < 0 { len + idx } else { idx };
+                if actual_idx < 0 || actual_idx >= len {
+                    return Err(self.fail(ErrorKind::Index, format!("Lys indeks buite perke: {} (lengte {})", idx, len)));
+                }
+                Ok(items[actual_idx as usize].clone())
+            }
+            (Value::String(s), Some(idx)) => {
+                let len = s.chars().count() as i64;
+                let actual_idx = if idx < 0 { len + idx } else { idx };
+                if actual_idx < 0 || actual_idx >= len {
+                    return Err(self.fail(ErrorKind::Index, format!("String indeks buite perke: {} (lengte {})", idx, len)));
+                }
+                let ch: String = s.chars().nth(actual_idx as usize).unwrap().to_string();
+                Ok(Value::String(Rc::new(ch)))
+            }
+            (Value::Woordeboek(dict), _) => match dict.get(&index)? {
+                Some(value) => Ok(value.clone()),
+                None => Err(self.fail(ErrorKind::Index, format!("Sleutel '{}' bestaan nie in woordeboek nie.", index))),
+            },
+            _ => Err(self.fail(ErrorKind::Type, "Kan slegs lyste, stringe en woordeboeke indekseer.".to_string())),
+        }
+    }
+
+    /// Order two values (see Value::compare); None when a NaN is involved
+    fn compare_values(&mut self, a: &Value, b: &Value, symbol: &str) -> Result<Option<Ordering>, String> {
+        a.compare(b).map_err(|e| self.fail(ErrorKind::Type, format!("{} ('{}').", e, symbol)))
+    }
+
+    /// Construct an ADT from named arguments: stack [constructor, values...]
+    fn call_named(&mut self, names: &[String]) -> Result<(), String> {
+        let callee_idx = self.stack.len() - names.len() - 1;
+        let tc = match &self.stack[callee_idx] {
+            Value::TypeConstructor(tc) => Rc::clone(tc),
+            _ => return Err("Benoemde argumente kan slegs met konstruktors gebruik word.".to_string()),
+        };
+
+        if names.len() != tc.arity {
+            return Err(self.fail(ErrorKind::Arguments, format!(
+                "Konstruktor '{}' verwag {} argumente maar het {} ontvang.",
+                tc.constructor_name, tc.arity, names.len()
+            )));
+        }
+
+        // Reorder the values into declaration order
+        let values = self.stack.split_off(callee_idx + 1);
+        let mut fields = vec![Value::Nil; tc.arity];
+        for (name, value) in names.iter().zip(values) {
+            let index = tc.field_index(name).ok_or_else(|| {
+                format!(
+                    "Konstruktor '{}' het geen veld '{}' nie.",
+                    tc.constructor_name, name
+                )
+            })?;
+            fields[index] = value;
+        }
+
+        self.stack.truncate(callee_idx);
+        self.push(Value::Adt(Rc::new(AdtInstance {
+            type_name: tc.type_name.clone(),
+            constructor_name: tc.constructor_name.clone(),
+            tag: tc.tag,
+            fields,
+            field_names: Rc::clone(&tc.field_names),
+        })));
+        Ok(())
+    }
+
+    /// Functional update of named fields: stack [adt, values...] -> [updated adt]
+    fn update_fields(&mut self, names: &[String]) -> Result<(), String> {
+        let values = self.stack.split_off(self.stack.len() - names.len());
+        let object = self.pop()?;
+
+        let Value::Adt(adt) = object else {
+            return Err(self.fail(ErrorKind::Type, "Kan slegs velde van ADT-waardes met 'met' opdateer.".to_string()));
+        };
+
+        let mut updated = adt.as_ref().clone();
+        for (name, value) in names.iter().zip(values) {
+            let index = adt.field_index(name).ok_or_else(|| {
+                format!(
+                    "Konstruktor '{}' het geen veld '{}' nie.",
+                    adt.constructor_name, name
+                )
+            })?;
+            updated.fields[index] = value;
+        }
+
+        self.push(Value::Adt(Rc::new(updated)));
+        Ok(())
+    }
+
+    /// Join two strings for '+', checking the size limit before allocating
+    fn concat(&mut self, a: &str, b: &str) -> Result<Value, String> {
+        self.check_size(a.len() + b.len())?;
+        Ok(Value::String(Rc::new(format!("{}{}", a, b))))
+    }
+
+    /// Remember the kind of a built-in error while it unwinds; the message
+    /// is what an uncaught error reports
+    fn fail(&mut self, kind: ErrorKind, message: String) -> String {
+        self.error_kind = Some(kind);
+        message
+    }
+
+    fn io_error(&mut self, e: io::Error) -> String {
+        self.fail(ErrorKind::InputOutput, format!("Invoer/uitvoer fout: {}", e))
+    }
+
+    /// Remember a raised value while it unwinds; the message is what an
+    /// uncaught raise reports
+    fn throw(&mut self, value: Value) -> String {
+        let message = match &value {
+            Value::Adt(adt) if adt.constructor_name == "Uitsondering" && adt.fields.len() == 3 => adt.fields[1].to_string(),
+            _ => format!("Ongevangde fout: {}", value),
+        };
+        self.thrown = Some(value);
+        message
+    }
+
+    /// Unwind to the innermost handler and push the caught value: the raised
+    /// value, or an Uitsondering describing a built-in error. Limit errors are never
+    /// caught so sandboxed code can't swallow them.
+    fn catch(&mut self, handlers: &mut Vec<Handler>, error: String) -> Result<Handler, String> {
+        let handler = match handlers.pop() {
+            Some(handler) if !self.limit_exceeded && self.exit_code.is_none() => handler,
+            _ => return Err(error),
+        };
+        let kind = self.error_kind.take().unwrap_or(ErrorKind::Runtime);
+        let value = match self.thrown.take() {
+            Some(value) => value,
+            None => construct(
+                "Uitsondering",
+                vec![
+                    Value::String(Rc::new(kind.name().to_string())),
+                    Value::String(Rc::new(error)),
+                    Value::Integer(self.line as i64),
+                ],
+            ),
+        };
+        self.close_upvalues(handler.stack_len);
+        self.stack.truncate(handler.stack_len);
+        self.frames.truncate(handler.frames_len);
+        self.push(value);
+        Ok(handler)
+    }
+
+    /// Call a native function after checking its arity
+    fn call_native(&mut self, nf: &NativeFunction, args: Vec<Value>) -> Result<Value, String> {
+        if !nf.arity.accepts(args.len()) {
+            return Err(self.fail(ErrorKind::Arguments, format!(
+                "{}() verwag {} argumente maar het {} ontvang.",
+                nf.name, nf.arity, args.len()
+            )));
+        }
+        let result = (nf.func)(self, &args)?;
+        self.check_value_size(&result)?;
+        Ok(result)
+    }
+
     /// Call a callable value with given arguments
     fn call_value(&mut self, callee: Value, args: Vec<Value>) -> Result<Value, String> {
         match callee {
             Value::Function(func) => {
                 if args.len() != func.arity {
-                    return Err(format!(
+                    return Err(self.fail(ErrorKind::Arguments, format!(
                         "Verwag {} argumente maar het {} ontvang.",
                         func.arity, args.len()
-                    ));
+                    )));
                 }
 
                 // Set up the call
@@ -1640,10 +3517,10 @@ impl VM {
             }
             Value::Closure(closure) => {
                 if args.len() != closure.function.arity {
-                    return Err(format!(
+                    return Err(self.fail(ErrorKind::Arguments, format!(
                         "Verwag {} argumente maar het {} ontvang.",
                         closure.function.arity, args.len()
-                    ));
+                    )));
                 }
 
                 // Set up the call
@@ -1669,30 +3546,24 @@ impl VM {
 
                 Ok(result)
             }
-            Value::NativeFunction(nf) => {
-                if args.len() != nf.arity {
-                    return Err(format!(
-                        "Verwag {} argumente maar het {} ontvang.",
-                        nf.arity, args.len()
-                    ));
-                }
-                (nf.func)(&args)
-            }
+            Value::NativeFunction(nf) => self.call_native(&nf, args),
             Value::TypeConstructor(tc) => {
                 if args.len() != tc.arity {
-                    return Err(format!(
+                    return Err(self.fail(ErrorKind::Arguments, format!(
                         "Konstruktor '{}' verwag {} argumente maar het {} ontvang.",
                         tc.constructor_name, tc.arity, args.len()
-                    ));
+                    )));
                 }
 
                 Ok(Value::Adt(Rc::new(AdtInstance {
                     type_name: tc.type_name.clone(),
                     constructor_name: tc.constructor_name.clone(),
+                    tag: tc.tag,
                     fields: args,
+                    field_names: Rc::clone(&tc.field_names),
                 })))
             }
-            _ => Err("Kan slegs funksies oproep.".to_string()),
+            _ => Err(self.fail(ErrorKind::Type, "Kan slegs funksies oproep.".to_string())),
         }
     }
 
@@ -1706,6 +3577,17 @@ impl VM {
         Ok(Value::List(Rc::new(results)))
     }
 
+    /// Turn the list produced by a higher-order function back into a set
+    fn set_from_list(&mut self, value: Value) -> Result<Value, String> {
+        match value {
+            Value::List(items) => {
+                let set = Set::from_values(items.iter().cloned()).map_err(|e| self.fail(ErrorKind::Type, e))?;
+                Ok(Value::Versameling(Rc::new(set)))
+            }
+            other => Ok(other),
+        }
+    }
+
     /// Higher-order function: filter
     fn hof_filter(&mut self, list: Rc<Vec<Value>>, func: Value) -> Result<Value, String> {
         let mut results = Vec::new();
@@ -1734,4 +3616,402 @@ impl VM {
         }
         Ok(Value::Nil)
     }
+
+    /// Higher-order function: sorteer_met (stable sort with a comparison function)
+    fn hof_sorteer_met(&mut self, list: Rc<Vec<Value>>, func: Value) -> Result<Value, String> {
+        let sorted = merge_sort(list.to_vec(), &mut |a, b| {
+            let result = self.call_value(func.clone(), vec![a.clone(), b.clone()])?;
+            match result.as_f64() {
+                Some(n) if n < 0.0 => Ok(Ordering::Less),
+                Some(n) if n > 0.0 => Ok(Ordering::Greater),
+                Some(_) => Ok(Ordering::Equal),
+                None => Err(self.fail(ErrorKind::Type, "sorteer_met() se funksie moet 'n nommer gee (soos vergelyk).".to_string())),
+            }
+        })?;
+        Ok(Value::List(Rc::new(sorted)))
+    }
+
+    /// Higher-order function: sorteer_volgens (stable sort by key)
+    fn hof_sorteer_volgens(&mut self, list: Rc<Vec<Value>>, func: Value) -> Result<Value, String> {
+        // Compute each key once, then sort the (key, element) pairs
+        let mut keyed = Vec::with_capacity(list.len());
+        for item in list.iter() {
+            let key = self.call_value(func.clone(), vec![item.clone()])?;
+            keyed.push(Value::Tuple(Rc::new(vec![key, item.clone()])));
+        }
+        let sorted = merge_sort(keyed, &mut |a, b| {
+            let (Value::Tuple(a), Value::Tuple(b)) = (a, b) else { unreachable!() };
+            match a[0].compare(&b[0]) {
+                Ok(Some(ordering)) => Ok(ordering),
+                Ok(None) => Err(self.fail(ErrorKind::Value, "sorteer_volgens() kan nie NaN sorteer nie.".to_string())),
+                Err(e) => Err(self.fail(ErrorKind::Type, format!("sorteer_volgens(): {}.", e))),
+            }
+        })?;
+        let items = sorted
+            .into_iter()
+            .map(|pair| match pair {
+                Value::Tuple(pair) => pair[1].clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+        Ok(Value::List(Rc::new(items)))
+    }
+
+    /// Position of the first element for which func is true (vind, indeks, enige)
+    fn hof_soek(&mut self, list: &[Value], func: Value) -> Result<Option<usize>, String> {
+        for (i, item) in list.iter().enumerate() {
+            if self.call_value(func.clone(), vec![item.clone()])?.is_truthy() {
+                return Ok(Some(i));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Higher-order function: groepeer (keys in order of first appearance)
+    fn hof_groepeer(&mut self, list: Rc<Vec<Value>>, func: Value) -> Result<Value, String> {
+        let mut groups: Vec<(Value, Vec<Value>)> = Vec::new();
+        let mut index: HashMap<HashKey, usize> = HashMap::new();
+        for item in list.iter() {
+            let key = self.call_value(func.clone(), vec![item.clone()])?;
+            let hash = key.hash_key().map_err(|e| self.fail(ErrorKind::Type, e))?;
+            match index.get(&hash) {
+                Some(&i) => groups[i].1.push(item.clone()),
+                None => {
+                    index.insert(hash, groups.len());
+                    groups.push((key, vec![item.clone()]));
+                }
+            }
+        }
+        let mut dict = Dictionary::new();
+        for (key, items) in groups {
+            dict.insert(key, Value::List(Rc::new(items))).map_err(|e| self.fail(ErrorKind::Type, e))?;
+        }
+        Ok(Value::Woordeboek(Rc::new(dict)))
+    }
+}
+
+/// A constructor name and its field names
+type BuiltinConstructor = (&'static str, &'static [&'static str]);
+
+/// Types every program starts with: (type, [(constructor, fields)])
+const BUILTIN_TYPES: &[(&str, &[BuiltinConstructor])] = &[
+    ("Opsie", &[("Niks", &[]), ("Sommige", &["waarde"])]),
+    ("Resultaat", &[("Ok", &["waarde"]), ("Fout", &["fout"])]),
+    ("Uitsondering", &[("Uitsondering", &["soort", "boodskap", "lyn"])]),
+];
+
+fn builtin_constructor(name: &str) -> TypeConstructorDef {
+    for (type_name, constructors) in BUILTIN_TYPES {
+        if let Some(tag) = constructors.iter().position(|(constructor, _)| *constructor == name) {
+            let fields = constructors[tag].1;
+            return TypeConstructorDef {
+                type_name: type_name.to_string(),
+                constructor_name: name.to_string(),
+                tag,
+                arity: fields.len(),
+                field_names: Rc::new(fields.iter().map(|field| field.to_string()).collect()),
+            };
+        }
+    }
+    unreachable!("no built-in constructor '{}'", name)
+}
+
+/// A value of a built-in type, built as if its constructor were called
+fn construct(name: &str, fields: Vec<Value>) -> Value {
+    let def = builtin_constructor(name);
+    Value::Adt(Rc::new(AdtInstance {
+        type_name: def.type_name,
+        constructor_name: def.constructor_name,
+        tag: def.tag,
+        fields,
+        field_names: def.field_names,
+    }))
+}
+
+fn opsie(value: Option<Value>) -> Value {
+    match value {
+        Some(value) => construct("Sommige", vec![value]),
+        None => construct("Niks", Vec::new()),
+    }
+}
+
+fn resultaat(result: Result<Value, String>) -> Value {
+    match result {
+        Ok(value) => construct("Ok", vec![value]),
+        Err(message) => construct("Fout", vec![Value::String(Rc::new(message))]),
+    }
+}
+
+/// The value inside a Sommige or Ok; Ok(None) for Niks and Fout, an error
+/// for anything that isn't an Opsie or Resultaat
+fn unwrap_success(value: &Value) -> Result<Option<Value>, String> {
+    match value {
+        Value::Adt(adt) if adt.fields.len() == 1 && matches!(adt.constructor_name.as_str(), "Sommige" | "Ok") => {
+            Ok(Some(adt.fields[0].clone()))
+        }
+        Value::Adt(adt) if adt.fields.len() == 1 && adt.constructor_name == "Fout" => Ok(None),
+        Value::Adt(adt) if adt.fields.is_empty() && adt.constructor_name == "Niks" => Ok(None),
+        Value::TypeConstructor(tc) if tc.constructor_name == "Niks" => Ok(None),
+        _ => Err(format!("'n Opsie of Resultaat, nie {} nie", value.type_name())),
+    }
+}
+
+/// Read a string the way a number literal is read, with an optional sign,
+/// so integers beyond i64 stay exact
+fn parse_number(value: &Value) -> Result<Value, Failure> {
+    match value {
+        Value::String(s) => {
+            let text = s.trim();
+            let (negative, digits) = match text.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, text.strip_prefix('+').unwrap_or(text)),
+            };
+            let chars: Vec<char> = digits.chars().collect();
+            let literal = match chars.first() {
+                Some(c) if c.is_ascii_digit() => match scan_number(&chars, 0) {
+                    Ok((literal, end)) if end == chars.len() => Some(literal),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match literal {
+                // Digits alone never exceed i64::MAX, so negating can't overflow
+                Some(NumberLiteral::Integer(n)) => Ok(Value::Integer(if negative { -n } else { n })),
+                Some(NumberLiteral::BigInteger(n)) => Ok(numeric::from_bigint(if negative { n.neg() } else { n })),
+                Some(NumberLiteral::Float(n)) => Ok(Value::Number(if negative { -n } else { n })),
+                None => Err((ErrorKind::Value, format!("na_nommer() kan nie '{}' as nommer lees nie.", s))),
+            }
+        }
+        value if value.as_f64().is_some() => Ok(value.clone()),
+        _ => Err((ErrorKind::Type, "na_nommer() verwag 'n string.".to_string())),
+    }
+}
+
+/// The built-in 'lêer' module. Failures are returned as Fout values; when the
+/// sandbox disallows file access every function except pad_saam raises an error.
+fn file_module() -> Value {
+    fn native(name: &str, arity: Arity, func: NativeFn) -> (String, Value) {
+        let full_name = format!("lêer.{}", name);
+        let checked: NativeFn = if name == "pad_saam" {
+            func
+        } else {
+            let full_name = full_name.clone();
+            Rc::new(move |vm, args| {
+                if !vm.limits.allow_file_io {
+                    return Err(vm.fail(ErrorKind::InputOutput, format!("Kan nie {}() gebruik nie: lêer toegang is hier nie toegelaat nie.", full_name)));
+                }
+                func(vm, args)
+            })
+        };
+        (
+            name.to_string(),
+            Value::NativeFunction(Rc::new(NativeFunction {
+                name: full_name,
+                arity,
+                func: checked,
+            })),
+        )
+    }
+
+    let exports = HashMap::from([
+        // lees(pad) - Ok(contents of the file)
+        native("lees", Arity::Exact(1), Rc::new(|vm, args| {
+            let (text, path) = path_arg(vm, &args[0], "lees")?;
+            let limit = vm.limits.max_collection_size;
+            let bytes = match std::fs::File::open(path).and_then(|file| read_bytes(file, limit)) {
+                Ok(bytes) => bytes,
+                Err(e) => return Ok(resultaat(Err(format!("Kan nie '{}' lees nie: {}", text, e)))),
+            };
+            vm.check_size(bytes.len())?;
+            Ok(resultaat(
+                String::from_utf8(bytes)
+                    .map(|contents| Value::String(Rc::new(contents)))
+                    .map_err(|e| format!("Kan nie '{}' lees nie: {}", text, e)),
+            ))
+        })),
+        // skryf(pad, teks) - replace the file's contents
+        native("skryf", Arity::Exact(2), Rc::new(|vm, args| {
+            let (text, path) = path_arg(vm, &args[0], "skryf")?;
+            let contents = string_arg(vm, &args[1], "lêer.skryf")?;
+            Ok(resultaat(
+                std::fs::write(path, contents.as_bytes())
+                    .map(|_| Value::Nil)
+                    .map_err(|e| format!("Kan nie na '{}' skryf nie: {}", text, e)),
+            ))
+        })),
+        // voeg_by_lêer(pad, teks) - append to the file, creating it if needed
+        native("voeg_by_lêer", Arity::Exact(2), Rc::new(|vm, args| {
+            let (text, path) = path_arg(vm, &args[0], "voeg_by_lêer")?;
+            let contents = string_arg(vm, &args[1], "lêer.voeg_by_lêer")?;
+            let result = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| file.write_all(contents.as_bytes()));
+            Ok(resultaat(
+                result
+                    .map(|_| Value::Nil)
+                    .map_err(|e| format!("Kan nie by '{}' voeg nie: {}", text, e)),
+            ))
+        })),
+        // bestaan(pad) - whether a file or directory exists
+        native("bestaan", Arity::Exact(1), Rc::new(|vm, args| {
+            let (_, path) = path_arg(vm, &args[0], "bestaan")?;
+            Ok(Value::Boolean(path.exists()))
+        })),
+        // lys_gids(pad) - Ok(sorted names of the directory's entries)
+        native("lys_gids", Arity::Exact(1), Rc::new(|vm, args| {
+            let (text, path) = path_arg(vm, &args[0], "lys_gids")?;
+            let names = std::fs::read_dir(path).and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+                    .collect::<io::Result<Vec<String>>>()
+            });
+            Ok(resultaat(
+                names
+                    .map(|mut names| {
+                        names.sort();
+                        Value::List(Rc::new(names.into_iter().map(|name| Value::String(Rc::new(name))).collect()))
+                    })
+                    .map_err(|e| format!("Kan nie gids '{}' lys nie: {}", text, e)),
+            ))
+        })),
+        // skep_gids(pad) - create a directory and any missing parents
+        native("skep_gids", Arity::Exact(1), Rc::new(|vm, args| {
+            let (text, path) = path_arg(vm, &args[0], "skep_gids")?;
+            Ok(resultaat(
+                std::fs::create_dir_all(path)
+                    .map(|_| Value::Nil)
+                    .map_err(|e| format!("Kan nie gids '{}' skep nie: {}", text, e)),
+            ))
+        })),
+        // verwyder(pad) - delete a file or an empty directory
+        native("verwyder", Arity::Exact(1), Rc::new(|vm, args| {
+            let (text, path) = path_arg(vm, &args[0], "verwyder")?;
+            let result = if path.is_dir() {
+                std::fs::remove_dir(path)
+            } else {
+                std::fs::remove_file(path)
+            };
+            Ok(resultaat(
+                result
+                    .map(|_| Value::Nil)
+                    .map_err(|e| format!("Kan nie '{}' verwyder nie: {}", text, e)),
+            ))
+        })),
+        // verwyder_alles(pad) - delete a file, or a directory with everything in it
+        native("verwyder_alles", Arity::Exact(1), Rc::new(|vm, args| {
+            let (text, path) = path_arg(vm, &args[0], "verwyder_alles")?;
+            let result = if path.is_dir() {
+                std::fs::remove_dir_all(path)
+            } else {
+                std::fs::remove_file(path)
+            };
+            Ok(resultaat(
+                result
+                    .map(|_| Value::Nil)
+                    .map_err(|e| format!("Kan nie '{}' verwyder nie: {}", text, e)),
+            ))
+        })),
+        // pad_saam(deel, ...) - join path parts
+        native("pad_saam", Arity::AtLeast(1), Rc::new(|vm, args| {
+            let mut path = PathBuf::new();
+            for part in args {
+                path.push(string_arg(vm, part, "lêer.pad_saam")?.as_str());
+            }
+            Ok(Value::String(Rc::new(path.to_string_lossy().into_owned())))
+        })),
+    ]);
+
+    Value::Module(Rc::new(Module {
+        name: String::from("lêer"),
+        path: String::from("lêer"),
+        exports,
+    }))
+}
+
+/// Read the rest of `reader`. Under a size limit at most one byte more than
+/// the limit is read, so an oversized input fails the size check without
+/// being held in memory.
+fn read_bytes(mut reader: impl Read, limit: Option<usize>) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match limit {
+        Some(max) => reader.take(max as u64 + 1).read_to_end(&mut bytes)?,
+        None => reader.read_to_end(&mut bytes)?,
+    };
+    Ok(bytes)
+}
+
+/// A path argument of a 'lêer' function, as given and resolved
+fn path_arg(vm: &mut VM, value: &Value, name: &str) -> Result<(Rc<String>, PathBuf), String> {
+    let text = string_arg(vm, value, &format!("lêer.{}", name))?;
+    let path = vm.resolve_path(&text)?;
+    Ok((text, path))
+}
+
+fn string_arg(vm: &mut VM, value: &Value, name: &str) -> Result<Rc<String>, String> {
+    match value {
+        Value::String(s) => Ok(Rc::clone(s)),
+        _ => Err(vm.fail(ErrorKind::Type, format!("{}() verwag 'n string, nie {} nie.", name, value.type_name()))),
+    }
+}
+
+fn float_arg(vm: &mut VM, value: &Value, name: &str) -> Result<f64, String> {
+    value.as_f64().ok_or_else(|| vm.fail(ErrorKind::Type, format!("{}() verwag 'n nommer, nie {} nie.", name, value.type_name())))
+}
+
+fn positive_arg(vm: &mut VM, value: &Value, name: &str) -> Result<f64, String> {
+    let x = float_arg(vm, value, name)?;
+    if x <= 0.0 {
+        return Err(vm.fail(ErrorKind::Value, format!("{}() verwag 'n positiewe nommer, nie {} nie.", name, value)));
+    }
+    Ok(x)
+}
+
+/// List argument of a higher-order list function
+fn list_arg(vm: &mut VM, value: &Value, name: &str) -> Result<Rc<Vec<Value>>, String> {
+    match value {
+        Value::List(list) => Ok(Rc::clone(list)),
+        _ => Err(vm.fail(ErrorKind::Type, format!("{}() verwag 'n lys as eerste argument.", name))),
+    }
+}
+
+/// Stable merge sort with a comparison that can fail (for example when it
+/// calls back into an Arkaan function). `slice::sort_by` can't be used
+/// because the comparison may be inconsistent or stop halfway.
+fn merge_sort<F>(items: Vec<Value>, compare: &mut F) -> Result<Vec<Value>, String>
+where
+    F: FnMut(&Value, &Value) -> Result<Ordering, String>,
+{
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+    let mut left = items;
+    let right = left.split_off(left.len() / 2);
+    let left = merge_sort(left, compare)?;
+    let right = merge_sort(right, compare)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        // Take from the left on ties to keep the sort stable
+        if compare(b, a)? == Ordering::Less {
+            merged.push(right.next().unwrap());
+        } else {
+            merged.push(left.next().unwrap());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
+/// Non-negative element count argument, as used by neem and los
+fn count_arg(vm: &mut VM, value: &Value, name: &str) -> Result<usize, String> {
+    match value {
+        Value::Integer(n) if *n >= 0 => Ok(*n as usize),
+        Value::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Ok(*n as usize),
+        _ => Err(vm.fail(ErrorKind::Type, format!("{}() verwag 'n nie-negatiewe heelgetal.", name))),
+    }
 }
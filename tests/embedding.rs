@@ -0,0 +1,139 @@
+//! Calling into Arkaan from Rust and back.
+
+use std::collections::HashMap;
+
+use arkaan_lang::{Arity, Error, ErrorKind, Interpreter, IntoValue, Value};
+
+#[test]
+fn eval_returns_the_final_expression() {
+    let mut arkaan = Interpreter::new();
+    assert_eq!(arkaan.eval("1 + 2").unwrap(), Value::Integer(3));
+    assert_eq!(arkaan.eval("laat x = 5").unwrap(), Value::Nil);
+}
+
+#[test]
+fn globals_persist_between_evals() {
+    let mut arkaan = Interpreter::new();
+    arkaan.eval("laat dubbel = fn(x) x * 2").unwrap();
+    arkaan.eval("laat tien = dubbel(5)").unwrap();
+    assert_eq!(arkaan.get::<i64>("tien").unwrap(), 10);
+    assert_eq!(arkaan.eval("dubbel(tien)").unwrap(), Value::Integer(20));
+}
+
+#[test]
+fn host_values_are_visible_to_scripts() {
+    let mut arkaan = Interpreter::new();
+    arkaan.set_global("naam", "Wêreld");
+    arkaan.set_global("getalle", vec![1i64, 2, 3]);
+    arkaan.eval("laat groet = \"Hallo \" + naam\nlaat som = getalle[0] + getalle[2]").unwrap();
+    assert_eq!(arkaan.get::<String>("groet").unwrap(), "Hallo Wêreld");
+    assert_eq!(arkaan.get::<i64>("som").unwrap(), 4);
+}
+
+#[test]
+fn get_converts_to_rust_types() {
+    let mut arkaan = Interpreter::new();
+    arkaan.eval("laat lys = [1, 2, 3]\nlaat wb = {\"a\": 1.5}").unwrap();
+    arkaan.set_global("niks", ());
+    assert_eq!(arkaan.get::<Vec<i64>>("lys").unwrap(), vec![1, 2, 3]);
+    assert_eq!(arkaan.get::<HashMap<String, f64>>("wb").unwrap()["a"], 1.5);
+    assert_eq!(arkaan.get::<Option<i64>>("niks").unwrap(), None);
+    assert!(matches!(arkaan.get::<String>("lys"), Err(Error::Runtime { kind: ErrorKind::Type, .. })));
+    assert!(matches!(arkaan.get::<i64>("bestaan_nie"), Err(Error::Runtime { kind: ErrorKind::Undefined, .. })));
+}
+
+#[test]
+fn call_runs_a_script_function() {
+    let mut arkaan = Interpreter::new();
+    arkaan.eval("laat optel = fn(a, b) a + b").unwrap();
+    let result = arkaan.call("optel", vec![2i64.into_value(), 3i64.into_value()]).unwrap();
+    assert_eq!(result, Value::Integer(5));
+    assert!(arkaan.call("optel", vec![Value::Integer(1)]).is_err());
+    assert!(arkaan.call("bestaan_nie", Vec::new()).is_err());
+}
+
+#[test]
+fn registered_functions_are_callable_from_scripts() {
+    let mut arkaan = Interpreter::new();
+    arkaan.register_fn("kwadraat", Arity::Exact(1), |args| match &args[0] {
+        Value::Integer(n) => Ok(Value::Integer(n * n)),
+        _ => Err("kwadraat verwag 'n heelgetal".to_string()),
+    });
+    assert_eq!(arkaan.eval("kwadraat(7)").unwrap(), Value::Integer(49));
+    assert_eq!(arkaan.eval("kaart([1, 2, 3], kwadraat)").unwrap().to_string(), "[1, 4, 9]");
+
+    let error = arkaan.eval("kwadraat(\"a\")").unwrap_err();
+    assert!(error.to_string().contains("kwadraat verwag 'n heelgetal"), "{}", error);
+
+    // Errors from the host can be caught like any other
+    let caught = arkaan.eval("probeer { kwadraat(\"a\") } vang (e) { \"gevang\" }");
+    assert!(caught.is_ok(), "{:?}", caught);
+}
+
+#[test]
+fn compile_errors_are_reported_before_running() {
+    let mut arkaan = Interpreter::new();
+    assert!(matches!(arkaan.compile("laat = 1"), Err(Error::Syntax(_))));
+    let program = arkaan.compile("laat x = 1 / 0").unwrap();
+    assert!(matches!(arkaan.run(&program), Err(Error::Runtime { .. })));
+    assert_eq!(arkaan.eval("2 * 3").unwrap(), Value::Integer(6));
+}
+
+#[test]
+fn errors_say_what_went_wrong() {
+    let mut arkaan = Interpreter::new();
+    assert!(matches!(arkaan.eval("laat = 1"), Err(Error::Syntax(_))));
+
+    match arkaan.eval("laat x = 1\nlaat y = x / 0").unwrap_err() {
+        Error::Runtime { kind, line, .. } => {
+            assert_eq!(kind, ErrorKind::DivisionByZero);
+            assert_eq!(line, 2);
+        }
+        error => panic!("{:?}", error),
+    }
+
+    match arkaan.eval("gooi Uitsondering(\"eie\", \"oeps\", 7)").unwrap_err() {
+        Error::Thrown { message, line, .. } => assert_eq!((message.as_str(), line), ("oeps", 7)),
+        error => panic!("{:?}", error),
+    }
+    assert_eq!(
+        arkaan.eval("gooi Uitsondering(\"indeks\", \"buite\", 3)").unwrap_err(),
+        Error::Runtime { kind: ErrorKind::Index, message: "buite".to_string(), line: 3 }
+    );
+    match arkaan.eval("gooi 42").unwrap_err() {
+        Error::Thrown { value, .. } => assert_eq!(value, Value::Integer(42)),
+        error => panic!("{:?}", error),
+    }
+
+    assert_eq!(arkaan.eval("verlaat(3)").unwrap_err(), Error::Exit(3));
+    assert!(matches!(arkaan.eval("verlaat(256)"), Err(Error::Runtime { kind: ErrorKind::Value, .. })));
+    assert!(matches!(arkaan.eval("verlaat(-1)"), Err(Error::Runtime { kind: ErrorKind::Value, .. })));
+    assert!(matches!(arkaan.call("bestaan_nie", Vec::new()), Err(Error::Runtime { .. })));
+}
+
+#[test]
+fn na_nommer_reads_integers_beyond_i64_exactly() {
+    let mut arkaan = Interpreter::new();
+    let big = arkaan.eval("na_nommer(\"123456789012345678901234567890\")").unwrap();
+    assert_eq!(big.to_string(), "123456789012345678901234567890");
+    assert_eq!(
+        arkaan.eval("na_nommer(\" -123456789012345678901234567890 \") == -123456789012345678901234567890").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(arkaan.eval("na_nommer(\"-42\")").unwrap(), Value::Integer(-42));
+    assert!(arkaan.eval("na_nommer(\"12abc\")").is_err());
+}
+
+#[test]
+fn range_patterns_compare_bounds_exactly() {
+    let mut arkaan = Interpreter::new();
+    arkaan
+        .eval("laat binne = fn(n) pas(n) {\n    geval 9007199254740993..18446744073709551616 => waar\n    geval _ => vals\n}")
+        .unwrap();
+    assert_eq!(arkaan.eval("binne(9007199254740992)").unwrap(), Value::Boolean(false));
+    assert_eq!(arkaan.eval("binne(9007199254740993)").unwrap(), Value::Boolean(true));
+    assert_eq!(arkaan.eval("binne(18446744073709551616)").unwrap(), Value::Boolean(true));
+    assert_eq!(arkaan.eval("binne(18446744073709551617)").unwrap(), Value::Boolean(false));
+    assert_eq!(arkaan.eval("binne(\"9007199254740993\")").unwrap(), Value::Boolean(false));
+    assert!(arkaan.eval("pas(1) { geval 9007199254740993..9007199254740992 => 1 }").is_err());
+}
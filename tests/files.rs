@@ -0,0 +1,48 @@
+//! The built-in 'lêer' module seen through the embedding API.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use arkaan_lang::{Interpreter, Value};
+
+/// A fresh directory holding one file, unique to the test
+fn directory_with_a_file(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("arkaan_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("notas.txt"), "inhoud").unwrap();
+    dir
+}
+
+fn interpreter_for(dir: &Path) -> Interpreter {
+    let mut arkaan = Interpreter::new();
+    arkaan.set_global("gids", dir.to_string_lossy().into_owned());
+    arkaan
+}
+
+#[test]
+fn verwyder_leaves_directories_that_are_not_empty() {
+    let dir = directory_with_a_file("verwyder");
+    let mut arkaan = interpreter_for(&dir);
+
+    assert_eq!(arkaan.eval("is_ok(lêer.verwyder(gids))").unwrap(), Value::Boolean(false));
+    assert!(dir.join("notas.txt").exists());
+
+    assert_eq!(
+        arkaan.eval("is_ok(lêer.verwyder(lêer.pad_saam(gids, \"notas.txt\")))").unwrap(),
+        Value::Boolean(true)
+    );
+    assert_eq!(arkaan.eval("is_ok(lêer.verwyder(gids))").unwrap(), Value::Boolean(true));
+    assert!(!dir.exists());
+}
+
+#[test]
+fn verwyder_alles_deletes_a_whole_tree() {
+    let dir = directory_with_a_file("verwyder_alles");
+    fs::create_dir_all(dir.join("binne")).unwrap();
+    let mut arkaan = interpreter_for(&dir);
+
+    assert_eq!(arkaan.eval("is_ok(lêer.verwyder_alles(gids))").unwrap(), Value::Boolean(true));
+    assert!(!dir.exists());
+    assert_eq!(arkaan.eval("is_ok(lêer.verwyder_alles(gids))").unwrap(), Value::Boolean(false));
+}
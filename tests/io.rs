@@ -0,0 +1,53 @@
+//! Redirected input and output seen through the embedding API.
+
+use std::io::Cursor;
+
+use arkaan_lang::{Interpreter, SharedBuffer};
+
+#[test]
+fn output_is_captured_in_a_shared_buffer() {
+    let output = SharedBuffer::new();
+    let errors = SharedBuffer::new();
+    let mut arkaan = Interpreter::new();
+    arkaan.set_output(output.clone());
+    arkaan.set_error_output(errors.clone());
+
+    arkaan.eval("druk(\"hallo\")\nskryf(1)\nskryf(2)\ndruk_fout(\"oeps\")").unwrap();
+    assert_eq!(output.contents(), "hallo\n12");
+    assert_eq!(errors.contents(), "oeps\n");
+
+    output.clear();
+    arkaan.eval("druk([1, 2])").unwrap();
+    assert_eq!(output.contents(), "[1, 2]\n");
+}
+
+#[test]
+fn input_is_read_line_by_line() {
+    let mut arkaan = Interpreter::new();
+    arkaan.set_input(Cursor::new("een\r\ntwee\n"));
+    assert_eq!(arkaan.eval("lees_lyn()").unwrap().to_string(), "een");
+    assert_eq!(arkaan.eval("lees_lyn()").unwrap().to_string(), "twee");
+    arkaan.eval("laat einde = lees_lyn()").unwrap();
+    assert_eq!(arkaan.get::<Option<String>>("einde").unwrap(), None);
+}
+
+#[test]
+fn input_can_be_read_all_at_once() {
+    let mut arkaan = Interpreter::new();
+    arkaan.set_input(Cursor::new("eerste\nres\nvan die invoer\n"));
+    arkaan.eval("laat kop = lees_lyn()\nlaat res = lees_alles()").unwrap();
+    assert_eq!(arkaan.get::<String>("kop").unwrap(), "eerste");
+    assert_eq!(arkaan.get::<String>("res").unwrap(), "res\nvan die invoer\n");
+    arkaan.eval("laat leeg = lees_alles()").unwrap();
+    assert_eq!(arkaan.get::<String>("leeg").unwrap(), "");
+}
+
+#[test]
+fn script_can_echo_its_input() {
+    let output = SharedBuffer::new();
+    let mut arkaan = Interpreter::new();
+    arkaan.set_output(output.clone());
+    arkaan.set_input(Cursor::new("3\n4\n"));
+    arkaan.eval("laat a = na_nommer(lees_lyn())\nlaat b = na_nommer(lees_lyn())\ndruk(a + b)").unwrap();
+    assert_eq!(output.contents(), "7\n");
+}
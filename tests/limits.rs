@@ -0,0 +1,146 @@
+//! Sandbox limits seen through the embedding API.
+
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+
+use arkaan_lang::{Error, Interpreter, Limits, SharedBuffer, Value};
+
+fn sandbox(limits: Limits) -> Interpreter {
+    Interpreter::with_limits(limits)
+}
+
+#[test]
+fn instruction_limit_stops_endless_loop() {
+    let mut arkaan = sandbox(Limits { max_instructions: Some(10_000), ..Limits::default() });
+    let error = arkaan.eval("terwyl (waar) { }").unwrap_err();
+    assert!(matches!(error, Error::Limit(_)), "{}", error);
+    assert!(error.to_string().contains("10000 instruksies"), "{}", error);
+}
+
+#[test]
+fn timeout_stops_endless_loop() {
+    let mut arkaan = sandbox(Limits { timeout: Some(Duration::from_millis(50)), ..Limits::default() });
+    let started = Instant::now();
+    let error = arkaan.eval("terwyl (waar) { }").unwrap_err();
+    assert!(matches!(error, Error::Limit(_)), "{}", error);
+    assert!(error.to_string().contains("tydlimiet"), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn timeout_is_checked_before_big_number_arithmetic() {
+    let mut arkaan = sandbox(Limits { timeout: Some(Duration::from_millis(200)), ..Limits::default() });
+    let started = Instant::now();
+    let error = arkaan
+        .eval("laat f = fn(x, n) as n == 0 x anders f(x * x, n - 1)\nf(3, 26)")
+        .unwrap_err();
+    assert!(matches!(error, Error::Limit(_)), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn depth_limit_stops_runaway_recursion() {
+    let mut arkaan = sandbox(Limits { max_call_depth: Some(50), ..Limits::default() });
+    arkaan.eval("laat diep = fn(n) as n == 0 0 anders 1 + diep(n - 1)").unwrap();
+    assert_eq!(arkaan.eval("diep(40)").unwrap(), Value::Integer(40));
+    let error = arkaan.eval("diep(100)").unwrap_err();
+    assert!(matches!(error, Error::Limit(_)), "{}", error);
+    assert!(error.to_string().contains("50 vlakke"), "{}", error);
+}
+
+#[test]
+fn size_limit_covers_collections_and_numbers() {
+    let mut arkaan = sandbox(Limits { max_collection_size: Some(100), ..Limits::default() });
+    assert!(matches!(arkaan.eval("herhaal(\"a\", 1000)"), Err(Error::Limit(_))));
+    assert!(matches!(arkaan.eval("mag(10, 500)"), Err(Error::Limit(_))));
+    assert_eq!(arkaan.eval("lengte(herhaal(\"a\", 50))").unwrap(), Value::Integer(50));
+
+    assert!(matches!(arkaan.eval("reeks(1000000000000)"), Err(Error::Limit(_))));
+    assert!(matches!(arkaan.eval("plat([reeks(60), reeks(60)])"), Err(Error::Limit(_))));
+
+    // Dictionaries and sets are measured as well, however they grow
+    arkaan.eval("laat groei = fn(d, lys) vou(lys, d, fn(d, i) met(d, i, i))").unwrap();
+    assert_eq!(arkaan.eval("lengte(groei(groei({}, reeks(50)), reeks(50, 100)))").unwrap(), Value::Integer(100));
+    assert!(matches!(arkaan.eval("groei(groei({}, reeks(60)), reeks(60, 120))"), Err(Error::Limit(_))));
+    assert!(matches!(arkaan.eval("versameling(ketting(reeks(60), reeks(60, 120)))"), Err(Error::Limit(_))));
+    assert!(matches!(arkaan.eval("vereniging(versameling(reeks(60)), versameling(reeks(60, 120)))"), Err(Error::Limit(_))));
+}
+
+#[test]
+fn size_limit_covers_reading_files_and_input() {
+    let path = std::env::temp_dir().join(format!("arkaan_groot_{}.txt", std::process::id()));
+    std::fs::write(&path, "a".repeat(1000)).unwrap();
+    let mut arkaan = sandbox(Limits { max_collection_size: Some(100), ..Limits::default() });
+    arkaan.set_global("pad", path.to_string_lossy().into_owned());
+    let error = arkaan.eval("lêer.lees(pad)").unwrap_err();
+    assert!(matches!(error, Error::Limit(_)), "{}", error);
+    std::fs::remove_file(&path).unwrap();
+
+    arkaan.set_input(Cursor::new("b".repeat(1000)));
+    let error = arkaan.eval("lees_alles()").unwrap_err();
+    assert!(matches!(error, Error::Limit(_)), "{}", error);
+
+    arkaan.set_input(Cursor::new("b".repeat(100)));
+    assert_eq!(arkaan.eval("lengte(lees_alles())").unwrap(), Value::Integer(100));
+}
+
+#[test]
+fn limit_errors_cannot_be_caught() {
+    let output = SharedBuffer::new();
+    let mut arkaan = sandbox(Limits { max_instructions: Some(10_000), ..Limits::default() });
+    arkaan.set_output(output.clone());
+    let error = arkaan
+        .eval("probeer {\n    terwyl (waar) { }\n} vang (e) {\n    druk(\"gevang\")\n}")
+        .unwrap_err();
+    assert!(matches!(error, Error::Limit(_)), "{}", error);
+
+    let mut arkaan = sandbox(Limits { max_call_depth: Some(20), ..Limits::default() });
+    arkaan.set_output(output.clone());
+    let error = arkaan
+        .eval("laat lus = fn(n) 1 + lus(n + 1)\nprobeer {\n    lus(0)\n} vang (e) {\n    druk(\"gevang\")\n}")
+        .unwrap_err();
+    assert!(matches!(error, Error::Limit(_)), "{}", error);
+    assert_eq!(output.contents(), "");
+}
+
+#[test]
+fn thrown_errors_that_look_like_limits_are_still_caught() {
+    let output = SharedBuffer::new();
+    let mut arkaan = sandbox(Limits { max_instructions: Some(10_000), ..Limits::default() });
+    arkaan.set_output(output.clone());
+    arkaan
+        .eval("probeer {\n    gooi Uitsondering(\"x\", \"Limiet oorskry: vals\", 1)\n} vang (e) {\n    druk(\"gevang\")\n}")
+        .unwrap();
+    assert_eq!(output.contents(), "gevang\n");
+
+    let error = arkaan.eval("gooi Uitsondering(\"x\", \"Limiet oorskry: vals\", 1)").unwrap_err();
+    assert!(matches!(error, Error::Thrown { .. }), "{}", error);
+}
+
+#[test]
+fn interpreter_is_usable_after_a_limit_error() {
+    let mut arkaan = sandbox(Limits { max_instructions: Some(10_000), ..Limits::default() });
+    assert!(arkaan.eval("terwyl (waar) { }").is_err());
+    assert_eq!(arkaan.eval("1 + 2").unwrap(), Value::Integer(3));
+}
+
+#[test]
+fn imports_can_be_disallowed() {
+    let mut arkaan = sandbox(Limits { allow_imports: false, ..Limits::default() });
+    let error = arkaan.eval("laai \"wiskunde.ark\" as w").unwrap_err();
+    assert!(error.to_string().contains("laai is hier nie toegelaat nie"), "{}", error);
+    assert!(matches!(error, Error::Runtime { .. }), "{}", error);
+}
+
+#[test]
+fn file_access_can_be_disallowed() {
+    let mut arkaan = sandbox(Limits { allow_file_io: false, ..Limits::default() });
+    let error = arkaan.eval("lêer.lees(\"Cargo.toml\")").unwrap_err();
+    assert!(error.to_string().contains("lêer toegang is hier nie toegelaat nie"), "{}", error);
+
+    // Building paths touches no files, so it stays allowed
+    assert_eq!(
+        arkaan.eval("lêer.pad_saam(\"a\", \"b\")").unwrap(),
+        Value::String(std::rc::Rc::new("a/b".to_string()))
+    );
+}